@@ -1,6 +1,9 @@
+use core::cmp::Ordering;
 use reborrow::{IntoConst, Reborrow, ReborrowMut, ReborrowTraits};
 
+#[allow(dead_code)]
 mod shared {
+    use core::cmp::Ordering;
     use reborrow::ReborrowCopyTraits;
 
     #[derive(ReborrowCopyTraits)]
@@ -19,6 +22,14 @@ mod shared {
         pub j: &'a T,
         pub k: &'b T,
     }
+
+    #[derive(ReborrowCopyTraits)]
+    pub enum ValueRef<'a> {
+        Scalar(&'a f64),
+        Complex { re: &'a f64, im: &'a f64 },
+        Tagged(Ordering, &'a f64),
+        Empty,
+    }
 }
 
 #[derive(ReborrowTraits)]
@@ -35,6 +46,40 @@ struct I32RefMut<'a, 'b> {
 #[Const(shared::I32TupleRef)]
 pub struct I32TupleRefMut<'a, 'b>(i32, #[reborrow] &'a mut i32, #[reborrow] &'b mut i32);
 
+#[derive(ReborrowTraits)]
+#[Const(shared::ValueRef)]
+enum ValueMut<'a> {
+    Scalar(#[reborrow] &'a mut f64),
+    Complex {
+        #[reborrow]
+        re: &'a mut f64,
+        #[reborrow]
+        im: &'a mut f64,
+    },
+    Tagged(Ordering, #[reborrow] &'a mut f64),
+    Empty,
+}
+
+fn scale(v: ValueMut<'_>, factor: f64) {
+    match v {
+        ValueMut::Scalar(x) | ValueMut::Tagged(_, x) => *x *= factor,
+        ValueMut::Complex { re, im } => {
+            *re *= factor;
+            *im *= factor;
+        }
+        ValueMut::Empty => {}
+    }
+}
+
+fn describe(v: shared::ValueRef<'_>) -> String {
+    match v {
+        shared::ValueRef::Scalar(x) => format!("{x}"),
+        shared::ValueRef::Complex { re, im } => format!("{re} + {im}i"),
+        shared::ValueRef::Tagged(ord, x) => format!("{x} ({ord:?})"),
+        shared::ValueRef::Empty => String::from("empty"),
+    }
+}
+
 fn main() {
     let i = 0;
     let j = &mut 0;
@@ -52,5 +97,28 @@ fn main() {
         let _unused = r.rb_mut();
         let _unused = r.into_const();
     }
+
+    {
+        let (mut re, mut im, mut x) = (1.0, 2.0, 3.0);
+        let values = [
+            ValueMut::Scalar(&mut x),
+            ValueMut::Complex {
+                re: &mut re,
+                im: &mut im,
+            },
+            ValueMut::Empty,
+        ];
+        for mut v in values {
+            scale(v.rb_mut(), 2.0);
+            scale(v.rb_mut(), 2.0);
+            println!("{}", describe(v.rb()));
+            println!("{}", describe(v.into_const()));
+        }
+
+        let mut y = 1.0;
+        let mut v = ValueMut::Tagged(Ordering::Less, &mut y);
+        scale(v.rb_mut(), 3.0);
+        println!("{}", describe(v.into_const()));
+    }
     println!("Hello, world!");
 }
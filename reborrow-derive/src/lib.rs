@@ -1,129 +1,215 @@
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::{DeriveInput, GenericParam, Lifetime, LifetimeDef};
 
-#[proc_macro_derive(ReborrowCopyTraits)]
+#[proc_macro_derive(ReborrowCopyTraits, attributes(reborrow))]
 pub fn derive_reborrow_copy(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as DeriveInput);
+    expand_reborrow_copy(input).into()
+}
 
+fn expand_reborrow_copy(input: DeriveInput) -> proc_macro2::TokenStream {
     let name = &input.ident;
+    let vis = &input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let options = Options::from_attrs(&input.attrs);
+    if options.has_mutable_variant_options() {
+        panic!("unsupported `reborrow` option for `ReborrowCopyTraits`.");
+    }
+    let has_field_attrs = match &input.data {
+        syn::Data::Struct(s) => s.fields.iter().any(is_reborrowable),
+        syn::Data::Enum(e) => e
+            .variants
+            .iter()
+            .flat_map(|v| &v.fields)
+            .any(is_reborrowable),
+        syn::Data::Union(u) => u.fields.named.iter().any(is_reborrowable),
+    };
+    if has_field_attrs {
+        panic!("`ReborrowCopyTraits` doesn't support `#[reborrow]` on fields.");
+    }
+    let inline = inline_attr(options.inline_always);
+    let krate = &options.krate;
+
+    let snapshot = if options.snapshot {
+        Some(quote! {
+            impl #impl_generics #name #ty_generics
+                #where_clause
+            {
+                /// Returns a copy of the view, with the same lifetime.
+                #inline
+                #[must_use]
+                #vis fn snapshot(&self) -> Self {
+                    *self
+                }
+            }
+        })
+    } else {
+        None
+    };
 
+    let impls = copy_impls(quote! { #name }, &input.generics, &inline, krate);
+
+    quote! {
+        #impls
+        #snapshot
+    }
+}
+
+/// Generates the `ReborrowCopyTraits` impls for the type `name`, with the given generics.
+fn copy_impls(
+    name: proc_macro2::TokenStream,
+    generics: &syn::Generics,
+    inline: &proc_macro2::TokenStream,
+    krate: &syn::Path,
+) -> proc_macro2::TokenStream {
     let reborrowed_lifetime = &LifetimeDef::new(Lifetime::new(
         "'__reborrow_lifetime",
         proc_macro2::Span::call_site(),
     ));
 
-    let mut target_ty_generics = input.generics.clone();
+    let mut target_ty_generics = generics.clone();
     for lt in target_ty_generics.lifetimes_mut() {
         *lt = reborrowed_lifetime.clone();
     }
     let target_ty_generics = target_ty_generics.split_for_impl().1;
-    let mut impl_generics = input.generics.clone();
+    let mut impl_generics = generics.clone();
     impl_generics
         .params
         .insert(0, GenericParam::Lifetime(reborrowed_lifetime.clone()));
     let impl_generics = impl_generics.split_for_impl().0;
 
-    let (orig_impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let (orig_impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
-    let expanded = quote! {
+    quote! {
         impl #orig_impl_generics ::core::marker::Copy for #name #ty_generics
             #where_clause {}
 
         impl #orig_impl_generics ::core::clone::Clone for #name #ty_generics
             #where_clause
         {
-            #[inline]
+            #inline
             fn clone(&self) -> Self {
                 *self
             }
         }
 
-        impl #orig_impl_generics ::reborrow::IntoConst for #name #ty_generics
+        impl #orig_impl_generics #krate::IntoConst for #name #ty_generics
             #where_clause
         {
             type Target = #name #ty_generics;
 
-            #[inline]
-            fn into_const(self) -> <Self as ::reborrow::IntoConst>::Target {
+            #inline
+            fn into_const(self) -> <Self as #krate::IntoConst>::Target {
                 self
             }
         }
 
-        impl #impl_generics ::reborrow::ReborrowMut<'__reborrow_lifetime> for #name #ty_generics
+        impl #impl_generics #krate::ReborrowMut<'__reborrow_lifetime> for #name #ty_generics
             #where_clause
         {
             type Target = #name #target_ty_generics;
 
-            #[inline]
-            fn rb_mut(&'__reborrow_lifetime mut self) -> <Self as ::reborrow::ReborrowMut>::Target {
+            #inline
+            fn rb_mut(&'__reborrow_lifetime mut self) -> <Self as #krate::ReborrowMut>::Target {
                 *self
             }
         }
 
-        impl #impl_generics ::reborrow::Reborrow<'__reborrow_lifetime> for #name #ty_generics
+        impl #impl_generics #krate::Reborrow<'__reborrow_lifetime> for #name #ty_generics
             #where_clause
         {
             type Target = #name #target_ty_generics;
 
-            #[inline]
-            fn rb(&'__reborrow_lifetime self) -> <Self as ::reborrow::Reborrow>::Target {
+            #inline
+            fn rb(&'__reborrow_lifetime self) -> <Self as #krate::Reborrow>::Target {
                 *self
             }
         }
 
-        impl #impl_generics ::reborrow::AsGeneralizedMut<
+        impl #impl_generics #krate::AsGeneralizedMut<
             '__reborrow_lifetime,
-            <Self as ::reborrow::ReborrowMut<'__reborrow_lifetime>>::Target,
+            <Self as #krate::ReborrowMut<'__reborrow_lifetime>>::Target,
         > for #name #ty_generics
             #where_clause
         {
-            #[inline]
-            fn as_generalized_mut(&'__reborrow_lifetime mut self) -> <Self as ::reborrow::ReborrowMut<'__reborrow_lifetime>>::Target {
+            #inline
+            fn as_generalized_mut(&'__reborrow_lifetime mut self) -> <Self as #krate::ReborrowMut<'__reborrow_lifetime>>::Target {
                 *self
             }
         }
 
-        impl #impl_generics ::reborrow::AsGeneralizedRef<
+        impl #impl_generics #krate::AsGeneralizedRef<
             '__reborrow_lifetime,
-            <Self as ::reborrow::Reborrow<'__reborrow_lifetime>>::Target,
+            <Self as #krate::Reborrow<'__reborrow_lifetime>>::Target,
         > for #name #ty_generics
             #where_clause
         {
-            #[inline]
-            fn as_generalized_ref(&'__reborrow_lifetime self) -> <Self as ::reborrow::Reborrow<'__reborrow_lifetime>>::Target {
+            #inline
+            fn as_generalized_ref(&'__reborrow_lifetime self) -> <Self as #krate::Reborrow<'__reborrow_lifetime>>::Target {
                 *self
             }
         }
-    };
-
-    expanded.into()
+    }
 }
 
 #[proc_macro_derive(ReborrowTraits, attributes(reborrow, Const))]
 pub fn derive_reborrow(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = syn::parse_macro_input!(input as DeriveInput);
+    expand_reborrow(input).into()
+}
 
-    let const_name = input
-        .attrs
-        .iter()
-        .find(|&attr| {
-            let segments = &attr.path.segments;
-            if let Some(syn::PathSegment {
-                ident,
-                arguments: syn::PathArguments::None,
-            }) = segments.first()
-            {
-                ident.to_string() == "Const"
-            } else {
-                false
-            }
-        })
-        .unwrap_or_else(|| panic!("Const reborrowed type must be specified."));
+fn expand_reborrow(input: DeriveInput) -> proc_macro2::TokenStream {
+    let options = Options::from_attrs(&input.attrs);
+    if options.snapshot {
+        panic!("`snapshot` is only supported by `ReborrowCopyTraits`.");
+    }
+    let inline = inline_attr(options.inline_always);
+    let krate = &options.krate;
+
+    let const_attr = input.attrs.iter().find(|&attr| {
+        let segments = &attr.path.segments;
+        if let Some(syn::PathSegment {
+            ident,
+            arguments: syn::PathArguments::None,
+        }) = segments.first()
+        {
+            *ident == "Const"
+        } else {
+            false
+        }
+    });
+
+    let transparent = if options.transparent {
+        if const_attr.is_some() {
+            panic!("`transparent` views must not specify a Const reborrowed type.");
+        }
+        Some(transparent_field(&input.data))
+    } else {
+        None
+    };
 
-    let const_name = const_name.tokens.clone();
-    let const_name = *syn::parse2::<syn::TypeParen>(const_name).unwrap().elem;
+    if options.mut_only {
+        if const_attr.is_some() {
+            panic!("`mut_only` views must not specify a Const reborrowed type.");
+        }
+        if transparent.is_some() || options.copy_const {
+            panic!("`mut_only` can't be used with `transparent` or `copy_const`.");
+        }
+    }
+
+    let const_name = match const_attr {
+        Some(attr) => match parse_const_type(attr) {
+            Ok(ty) => Some(ty),
+            Err(err) => return err.to_compile_error(),
+        },
+        None if transparent.is_some() || options.mut_only => None,
+        None => panic!("Const reborrowed type must be specified."),
+    };
 
     let name = &input.ident;
+    let vis = &input.vis;
 
     let reborrowed_lifetime = &LifetimeDef::new(Lifetime::new(
         "'__reborrow_lifetime",
@@ -144,7 +230,56 @@ pub fn derive_reborrow(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
 
     let (orig_impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
-    let (rb_mut, rb, into_const) = {
+    let field_tuple = if options.field_tuple {
+        let fields = match &input.data {
+            syn::Data::Struct(s) => &s.fields,
+            _ => panic!("`field_tuple` is only supported on structs."),
+        };
+        let (tys, exprs): (Vec<_>, Vec<_>) = fields
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| is_reborrowable(f))
+            .map(|(i, f)| {
+                let options = FieldOptions::from_attrs(&f.attrs);
+                if options.with.is_some() || options.with_const.is_some() {
+                    panic!("`field_tuple` can't be used with fields reborrowed `with` a function.");
+                }
+                let idx = syn::Index::from(i);
+                let expr = f
+                    .ident
+                    .as_ref()
+                    .map(|ident| quote! { self.#ident })
+                    .unwrap_or(quote! { self.#idx });
+                (&f.ty, expr)
+            })
+            .unzip();
+
+        Some(quote! {
+            impl #orig_impl_generics #name #ty_generics
+                #where_clause
+            {
+                #inline
+                #[must_use]
+                #vis fn reborrow_fields<'__reborrow_lifetime>(
+                    &'__reborrow_lifetime mut self,
+                ) -> ( #(<#tys as #krate::ReborrowMut<'__reborrow_lifetime>>::Target,)* ) {
+                    ( #(<#tys as #krate::ReborrowMut>::rb_mut(&mut #exprs),)* )
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    let (rb_mut, rb, into_const) = if let Some((member, ty)) = &transparent {
+        (
+            quote! { #name { #member: <#ty as #krate::ReborrowMut>::rb_mut(&mut self.#member) } },
+            quote! { <#ty as #krate::Reborrow>::rb(&self.#member) },
+            quote! { <#ty as #krate::IntoConst>::into_const(self.#member) },
+        )
+    } else {
+        // only the `rb_mut` expression is used by `mut_only` views, which have no const variant.
+        let const_name = &const_name;
         let data = input.data;
 
         match data {
@@ -155,100 +290,455 @@ pub fn derive_reborrow(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
                         f.named
                             .iter()
                             .enumerate()
-                            .map(|(i, f)| reborrow_exprs(i, f.clone())),
+                            .map(|(i, f)| reborrow_exprs(i, f.clone(), krate)),
                     );
 
-                    (
-                        quote! { #name:: #target_ty_generics { #(#names: #f0,)* } },
-                        quote! { #const_name:: #target_ty_generics { #(#names: #f1,)* } },
-                        quote! { #const_name:: #ty_generics { #(#names: #f2,)* } },
-                    )
+                    match &options.const_ctor {
+                        Some(ctor) => (
+                            quote! { #name:: #target_ty_generics { #(#names: #f0,)* } },
+                            quote! { #ctor( #(#f1,)* ) },
+                            quote! { #ctor( #(#f2,)* ) },
+                        ),
+                        None => (
+                            quote! { #name:: #target_ty_generics { #(#names: #f0,)* } },
+                            quote! { #const_name:: #target_ty_generics { #(#names: #f1,)* } },
+                            quote! { #const_name:: #ty_generics { #(#names: #f2,)* } },
+                        ),
+                    }
                 }
                 syn::Fields::Unnamed(f) => {
                     let (f0, f1, f2) = unzip3(
                         f.unnamed
                             .iter()
                             .enumerate()
-                            .map(|(i, f)| reborrow_exprs(i, f.clone())),
+                            .map(|(i, f)| reborrow_exprs(i, f.clone(), krate)),
                     );
 
-                    (
-                        quote! { #name:: #target_ty_generics ( #(#f0,)* ) },
-                        quote! { #const_name:: #target_ty_generics ( #(#f1,)* ) },
-                        quote! { #const_name:: #ty_generics ( #(#f2,)* ) },
-                    )
+                    match &options.const_ctor {
+                        Some(ctor) => (
+                            quote! { #name:: #target_ty_generics ( #(#f0,)* ) },
+                            quote! { #ctor( #(#f1,)* ) },
+                            quote! { #ctor( #(#f2,)* ) },
+                        ),
+                        None => (
+                            quote! { #name:: #target_ty_generics ( #(#f0,)* ) },
+                            quote! { #const_name:: #target_ty_generics ( #(#f1,)* ) },
+                            quote! { #const_name:: #ty_generics ( #(#f2,)* ) },
+                        ),
+                    }
                 }
-                syn::Fields::Unit => (
-                    quote! { #name:: #target_ty_generics },
-                    quote! { #const_name:: #target_ty_generics },
-                    quote! { #const_name:: #target_ty_generics },
-                ),
+                syn::Fields::Unit => match &options.const_ctor {
+                    Some(ctor) => (
+                        quote! { #name:: #target_ty_generics },
+                        quote! { #ctor() },
+                        quote! { #ctor() },
+                    ),
+                    None => (
+                        quote! { #name:: #target_ty_generics },
+                        quote! { #const_name:: #target_ty_generics },
+                        quote! { #const_name:: #target_ty_generics },
+                    ),
+                },
             },
-            syn::Data::Enum(_) => panic!("reborrow-derive does not support enums."),
+            syn::Data::Enum(_) if options.const_ctor.is_some() => {
+                panic!("`const_ctor` is only supported on structs.")
+            }
+            syn::Data::Enum(e) => {
+                let (arms0, arms1, arms2) = unzip3(e.variants.iter().map(|v| {
+                    let variant = &v.ident;
+                    let bindings: Vec<_> = v
+                        .fields
+                        .iter()
+                        .enumerate()
+                        .map(|(i, f)| {
+                            f.ident
+                                .clone()
+                                .unwrap_or_else(|| format_ident!("__field{}", i))
+                        })
+                        .collect();
+                    let (f0, f1, f2) = unzip3(
+                        v.fields
+                            .iter()
+                            .zip(&bindings)
+                            .map(|(f, b)| field_exprs(f.clone(), krate, quote! { *#b }, quote! { *#b }, quote! { #b })),
+                    );
+
+                    match v.fields {
+                        syn::Fields::Named(_) => (
+                            quote! { #name::#variant { #(#bindings,)* } => #name::#variant { #(#bindings: #f0,)* } },
+                            quote! { #name::#variant { #(#bindings,)* } => #const_name::#variant { #(#bindings: #f1,)* } },
+                            quote! { #name::#variant { #(#bindings,)* } => #const_name::#variant { #(#bindings: #f2,)* } },
+                        ),
+                        syn::Fields::Unnamed(_) => (
+                            quote! { #name::#variant ( #(#bindings,)* ) => #name::#variant ( #(#f0,)* ) },
+                            quote! { #name::#variant ( #(#bindings,)* ) => #const_name::#variant ( #(#f1,)* ) },
+                            quote! { #name::#variant ( #(#bindings,)* ) => #const_name::#variant ( #(#f2,)* ) },
+                        ),
+                        syn::Fields::Unit => (
+                            quote! { #name::#variant => #name::#variant },
+                            quote! { #name::#variant => #const_name::#variant },
+                            quote! { #name::#variant => #const_name::#variant },
+                        ),
+                    }
+                }));
+
+                (
+                    quote! { match self { #(#arms0,)* } },
+                    quote! { match self { #(#arms1,)* } },
+                    quote! { match self { #(#arms2,)* } },
+                )
+            }
             syn::Data::Union(_) => panic!("reborrow-derive does not support unions."),
         }
     };
 
-    let expanded = quote! {
-        impl #orig_impl_generics ::reborrow::IntoConst for #name #ty_generics
-            #where_clause
-        {
-            type Target = #const_name #ty_generics;
-
-            #[inline]
-            fn into_const(self) -> <Self as ::reborrow::IntoConst>::Target {
-                #into_const
+    let renamed = options.rename.map(|method| {
+        quote! {
+            impl #orig_impl_generics #name #ty_generics
+                #where_clause
+            {
+                #inline
+                #[must_use]
+                #vis fn #method(&mut self) -> <Self as #krate::ReborrowMut<'_>>::Target {
+                    <Self as #krate::ReborrowMut>::rb_mut(self)
+                }
             }
         }
+    });
+
+    let long_names = if options.long_names {
+        let reborrow = if options.mut_only {
+            None
+        } else {
+            Some(quote! {
+                #inline
+                #[must_use]
+                #vis fn reborrow(&self) -> <Self as #krate::Reborrow<'_>>::Target {
+                    <Self as #krate::Reborrow>::rb(self)
+                }
+            })
+        };
 
-        impl #impl_generics ::reborrow::ReborrowMut<'__reborrow_lifetime> for #name #ty_generics
-            #where_clause
-        {
-            type Target = #name #target_ty_generics;
+        Some(quote! {
+            impl #orig_impl_generics #name #ty_generics
+                #where_clause
+            {
+                #reborrow
 
-            #[inline]
-            fn rb_mut(&'__reborrow_lifetime mut self) -> <Self as ::reborrow::ReborrowMut>::Target {
-                #rb_mut
+                #inline
+                #[must_use]
+                #vis fn reborrow_mut(&mut self) -> <Self as #krate::ReborrowMut<'_>>::Target {
+                    <Self as #krate::ReborrowMut>::rb_mut(self)
+                }
             }
+        })
+    } else {
+        None
+    };
+
+    let copy_const = if options.copy_const {
+        if transparent.is_some() {
+            panic!("`copy_const` can't be used with `transparent`.");
         }
+        Some(copy_impls(
+            quote! { #const_name },
+            &input.generics,
+            &inline,
+            krate,
+        ))
+    } else {
+        None
+    };
+
+    let (const_target, const_rb_target) = match &transparent {
+        Some((_, ty)) => (
+            quote! { <#ty as #krate::IntoConst>::Target },
+            quote! { <#ty as #krate::Reborrow<'__reborrow_lifetime>>::Target },
+        ),
+        None => (
+            quote! { #const_name #ty_generics },
+            quote! { #const_name #target_ty_generics },
+        ),
+    };
+
+    // the const target of a transparent view may be a reference, in which case this would overlap
+    // with the blanket impl for `AsRef` types.
+    let as_generalized_ref = if transparent.is_none() && !options.mut_only {
+        Some(quote! {
+            impl #impl_generics #krate::AsGeneralizedRef<
+                '__reborrow_lifetime,
+                <Self as #krate::Reborrow<'__reborrow_lifetime>>::Target,
+            > for #name #ty_generics
+                #where_clause
+            {
+                #inline
+                fn as_generalized_ref(&'__reborrow_lifetime self) -> <Self as #krate::Reborrow<'__reborrow_lifetime>>::Target {
+                    <Self as #krate::Reborrow>::rb(self)
+                }
+            }
+        })
+    } else {
+        None
+    };
+
+    // the fields of views with a `Drop` impl can't be moved out, so these can opt out of `IntoConst`.
+    let into_const = if options.no_into_const || options.mut_only {
+        None
+    } else {
+        Some(quote! {
+            impl #orig_impl_generics #krate::IntoConst for #name #ty_generics
+                #where_clause
+            {
+                type Target = #const_target;
+
+                #inline
+                fn into_const(self) -> <Self as #krate::IntoConst>::Target {
+                    #into_const
+                }
+            }
+        })
+    };
+
+    let reborrow = if options.mut_only {
+        None
+    } else {
+        Some(quote! {
+            impl #impl_generics #krate::Reborrow<'__reborrow_lifetime> for #name #ty_generics
+                #where_clause
+            {
+                type Target = #const_rb_target;
+
+                #inline
+                fn rb(&'__reborrow_lifetime self) -> <Self as #krate::Reborrow>::Target {
+                    #rb
+                }
+            }
+        })
+    };
 
-        impl #impl_generics ::reborrow::Reborrow<'__reborrow_lifetime> for #name #ty_generics
+    quote! {
+        #into_const
+
+        impl #impl_generics #krate::ReborrowMut<'__reborrow_lifetime> for #name #ty_generics
             #where_clause
         {
-            type Target = #const_name #target_ty_generics;
+            type Target = #name #target_ty_generics;
 
-            #[inline]
-            fn rb(&'__reborrow_lifetime self) -> <Self as ::reborrow::Reborrow>::Target {
-                #rb
+            #inline
+            fn rb_mut(&'__reborrow_lifetime mut self) -> <Self as #krate::ReborrowMut>::Target {
+                #rb_mut
             }
         }
 
-        impl #impl_generics ::reborrow::AsGeneralizedMut<
+        #reborrow
+
+        impl #impl_generics #krate::AsGeneralizedMut<
             '__reborrow_lifetime,
-            <Self as ::reborrow::ReborrowMut<'__reborrow_lifetime>>::Target,
+            <Self as #krate::ReborrowMut<'__reborrow_lifetime>>::Target,
         > for #name #ty_generics
             #where_clause
         {
-            #[inline]
-            fn as_generalized_mut(&'__reborrow_lifetime mut self) -> <Self as ::reborrow::ReborrowMut<'__reborrow_lifetime>>::Target {
-                <Self as ::reborrow::ReborrowMut>::rb_mut(self)
+            #inline
+            fn as_generalized_mut(&'__reborrow_lifetime mut self) -> <Self as #krate::ReborrowMut<'__reborrow_lifetime>>::Target {
+                <Self as #krate::ReborrowMut>::rb_mut(self)
             }
         }
 
-        impl #impl_generics ::reborrow::AsGeneralizedRef<
-            '__reborrow_lifetime,
-            <Self as ::reborrow::Reborrow<'__reborrow_lifetime>>::Target,
-        > for #name #ty_generics
-            #where_clause
-        {
-            #[inline]
-            fn as_generalized_ref(&'__reborrow_lifetime self) -> <Self as ::reborrow::Reborrow<'__reborrow_lifetime>>::Target {
-                <Self as ::reborrow::Reborrow>::rb(self)
+        #as_generalized_ref
+
+        #renamed
+        #long_names
+        #copy_const
+        #field_tuple
+    }
+}
+
+/// Returns the inlining attribute of the generated methods.
+fn inline_attr(inline_always: bool) -> proc_macro2::TokenStream {
+    if inline_always {
+        quote! { #[inline(always)] }
+    } else {
+        quote! { #[inline] }
+    }
+}
+
+/// Parses the const variant from either `#[Const(Type)]` or `#[Const = "Type"]`.
+fn parse_const_type(attr: &syn::Attribute) -> syn::Result<syn::Type> {
+    let parser = |input: syn::parse::ParseStream| {
+        let ty = if input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in input);
+            let ty = content.parse()?;
+            if !content.is_empty() {
+                return Err(content.error("unexpected tokens after the const type"));
             }
+            ty
+        } else {
+            input.parse::<syn::Token![=]>()?;
+            input.parse::<syn::LitStr>()?.parse()?
+        };
+        if !input.is_empty() {
+            return Err(input.error("unexpected tokens after the const type"));
         }
+        Ok(ty)
     };
 
-    expanded.into()
+    syn::parse::Parser::parse2(parser, attr.tokens.clone()).map_err(|_| {
+        syn::Error::new_spanned(attr, "expected `#[Const(Type)]` or `#[Const = \"Type\"]`")
+    })
+}
+
+/// Struct-level options, given as `#[reborrow(...)]` on the mutable variant, or on the const
+/// variant for the ones supported by `ReborrowCopyTraits`.
+struct Options {
+    /// Name of an inherent method forwarding to `rb_mut`.
+    rename: Option<syn::Ident>,
+    /// Whether to also generate the `ReborrowCopyTraits` impls for the const variant.
+    copy_const: bool,
+    /// Whether to generate a `reborrow_fields` method returning the reborrowed fields as a tuple.
+    field_tuple: bool,
+    /// Whether the view is a single field wrapper, whose const variant is the one of its field.
+    transparent: bool,
+    /// Function constructing the const variant from its fields, in declaration order.
+    const_ctor: Option<syn::Path>,
+    /// Whether to skip the `IntoConst` impl.
+    no_into_const: bool,
+    /// Whether to mark the generated methods `#[inline(always)]` instead of `#[inline]`.
+    inline_always: bool,
+    /// Path to the `reborrow` crate in the generated code.
+    krate: syn::Path,
+    /// Whether to generate inherent `reborrow` and `reborrow_mut` methods forwarding to `rb` and
+    /// `rb_mut`.
+    long_names: bool,
+    /// Whether to only generate the `ReborrowMut` impls, for views without a const variant.
+    mut_only: bool,
+    /// Whether to generate a `snapshot` method returning a copy of a `Copy` view.
+    snapshot: bool,
+}
+
+impl Options {
+    fn from_attrs(attrs: &[syn::Attribute]) -> Self {
+        let mut options = Options {
+            rename: None,
+            copy_const: false,
+            field_tuple: false,
+            transparent: false,
+            const_ctor: None,
+            no_into_const: false,
+            inline_always: false,
+            krate: syn::parse_quote! { ::reborrow },
+            long_names: false,
+            mut_only: false,
+            snapshot: false,
+        };
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("reborrow")) {
+            let list = match attr.parse_meta() {
+                Ok(syn::Meta::List(list)) => list,
+                _ => panic!("expected `#[reborrow(...)]`."),
+            };
+
+            for nested in list.nested {
+                match nested {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        path,
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    })) if path.is_ident("rename") => {
+                        options.rename =
+                            Some(lit.parse().unwrap_or_else(|_| {
+                                panic!("`rename` must be a valid identifier.")
+                            }));
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        path,
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    })) if path.is_ident("const_ctor") => {
+                        options.const_ctor = Some(
+                            lit.parse()
+                                .unwrap_or_else(|_| panic!("`const_ctor` must be a valid path.")),
+                        );
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        path,
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    })) if path.is_ident("crate") => {
+                        options.krate = lit
+                            .parse()
+                            .unwrap_or_else(|_| panic!("`crate` must be a valid path."));
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("copy_const") => {
+                        options.copy_const = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(path))
+                        if path.is_ident("field_tuple") =>
+                    {
+                        options.field_tuple = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(path))
+                        if path.is_ident("transparent") =>
+                    {
+                        options.transparent = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(path))
+                        if path.is_ident("no_into_const") =>
+                    {
+                        options.no_into_const = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(path))
+                        if path.is_ident("inline_always") =>
+                    {
+                        options.inline_always = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("long_names") => {
+                        options.long_names = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("mut_only") => {
+                        options.mut_only = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("snapshot") => {
+                        options.snapshot = true;
+                    }
+                    _ => panic!("unknown `reborrow` option."),
+                }
+            }
+        }
+
+        options
+    }
+
+    /// Returns whether any option specific to the mutable variant is set.
+    fn has_mutable_variant_options(&self) -> bool {
+        self.rename.is_some()
+            || self.copy_const
+            || self.field_tuple
+            || self.transparent
+            || self.const_ctor.is_some()
+            || self.no_into_const
+            || self.long_names
+            || self.mut_only
+    }
+}
+
+/// Returns the member and type of the only field of a `transparent` view.
+fn transparent_field(data: &syn::Data) -> (syn::Member, syn::Type) {
+    let fields = match data {
+        syn::Data::Struct(s) => &s.fields,
+        _ => panic!("`transparent` is only supported on structs."),
+    };
+    let mut fields = fields.iter();
+    match (fields.next(), fields.next()) {
+        (Some(f), None) => {
+            let member = match &f.ident {
+                Some(ident) => syn::Member::Named(ident.clone()),
+                None => syn::Member::Unnamed(syn::Index::from(0)),
+            };
+            (member, f.ty.clone())
+        }
+        _ => panic!("`transparent` views must have exactly one field."),
+    }
 }
 
 fn unzip3<A, B, C, I: Iterator<Item = (A, B, C)>>(iter: I) -> (Vec<A>, Vec<B>, Vec<C>) {
@@ -266,13 +756,116 @@ fn unzip3<A, B, C, I: Iterator<Item = (A, B, C)>>(iter: I) -> (Vec<A>, Vec<B>, V
 fn reborrow_exprs(
     idx: usize,
     f: syn::Field,
+    krate: &syn::Path,
 ) -> (
     proc_macro2::TokenStream,
     proc_macro2::TokenStream,
     proc_macro2::TokenStream,
 ) {
-    let is_reborrowable = f
-        .attrs
+    let idx = syn::Index::from(idx);
+
+    let expr = f
+        .ident
+        .as_ref()
+        .map(|ident| quote! { self.#ident })
+        .unwrap_or(quote! { self.#idx });
+
+    field_exprs(f, krate, expr.clone(), expr.clone(), expr)
+}
+
+/// Builds the `rb_mut`, `rb` and `into_const` expressions of a field, given the place expressions
+/// through which it is accessed in each of them.
+fn field_exprs(
+    f: syn::Field,
+    krate: &syn::Path,
+    mut_place: proc_macro2::TokenStream,
+    ref_place: proc_macro2::TokenStream,
+    owned_place: proc_macro2::TokenStream,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    if !is_reborrowable(&f) {
+        (mut_place, ref_place, owned_place)
+    } else {
+        let options = FieldOptions::from_attrs(&f.attrs);
+        let ty = f.ty;
+        // spanned to the field type, so that errors about it not being reborrowable point to it
+        let span = ty.span();
+        (
+            match options.with {
+                Some(with) => quote_spanned! {span=> #with(&mut #mut_place) },
+                None => {
+                    quote_spanned! {span=> <#ty as #krate::ReborrowMut>::rb_mut(&mut #mut_place) }
+                }
+            },
+            match options.with_const {
+                Some(with_const) => quote_spanned! {span=> #with_const(&#ref_place) },
+                None => quote_spanned! {span=> <#ty as #krate::Reborrow>::rb(&#ref_place) },
+            },
+            quote_spanned! {span=> <#ty as #krate::IntoConst>::into_const(#owned_place) },
+        )
+    }
+}
+
+/// Field-level options, given as `#[reborrow(...)]` on a field of the mutable variant.
+struct FieldOptions {
+    /// Function reborrowing the field mutably, called instead of `ReborrowMut::rb_mut`.
+    with: Option<syn::Path>,
+    /// Function reborrowing the field immutably, called instead of `Reborrow::rb`.
+    with_const: Option<syn::Path>,
+}
+
+impl FieldOptions {
+    fn from_attrs(attrs: &[syn::Attribute]) -> Self {
+        let mut options = FieldOptions {
+            with: None,
+            with_const: None,
+        };
+
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("reborrow")) {
+            if attr.tokens.is_empty() {
+                continue;
+            }
+            let list = match attr.parse_meta() {
+                Ok(syn::Meta::List(list)) => list,
+                _ => panic!("expected `#[reborrow]` or `#[reborrow(...)]`."),
+            };
+
+            for nested in list.nested {
+                match nested {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        path,
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    })) if path.is_ident("with") => {
+                        options.with = Some(
+                            lit.parse()
+                                .unwrap_or_else(|_| panic!("`with` must be a valid path.")),
+                        );
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                        path,
+                        lit: syn::Lit::Str(lit),
+                        ..
+                    })) if path.is_ident("with_const") => {
+                        options.with_const = Some(
+                            lit.parse()
+                                .unwrap_or_else(|_| panic!("`with_const` must be a valid path.")),
+                        );
+                    }
+                    _ => panic!("unknown `reborrow` field option."),
+                }
+            }
+        }
+
+        options
+    }
+}
+
+fn is_reborrowable(f: &syn::Field) -> bool {
+    f.attrs
         .iter()
         .find(|&attr| {
             let segments = &attr.path.segments;
@@ -281,28 +874,55 @@ fn reborrow_exprs(
                 arguments: syn::PathArguments::None,
             }) = segments.first()
             {
-                ident.to_string() == "reborrow"
+                *ident == "reborrow"
             } else {
                 false
             }
         })
-        .is_some();
+        .is_some()
+}
 
-    let idx = syn::Index::from(idx);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let expr = f
-        .ident
-        .map(|ident| quote! { self.#ident })
-        .unwrap_or(quote! { self.#idx });
+    fn count(expanded: &proc_macro2::TokenStream, attr: proc_macro2::TokenStream) -> usize {
+        expanded.to_string().matches(&attr.to_string()).count()
+    }
 
-    if !is_reborrowable {
-        (quote! {#expr}, quote! {#expr}, quote! {#expr})
-    } else {
-        let ty = f.ty;
-        (
-            quote! { <#ty as ::reborrow::ReborrowMut>::rb_mut(&mut #expr) },
-            quote! { <#ty as ::reborrow::Reborrow>::rb(&#expr) },
-            quote! { <#ty as ::reborrow::IntoConst>::into_const(#expr) },
-        )
+    #[test]
+    fn inline_always() {
+        let inline = quote! { #[inline] };
+        let inline_always = quote! { #[inline(always)] };
+
+        let expanded = expand_reborrow(syn::parse_quote! {
+            #[Const(ViewRef)]
+            struct ViewMut<'a> {
+                #[reborrow]
+                x: &'a mut i32,
+            }
+        });
+        assert!(count(&expanded, inline.clone()) > 0);
+        assert_eq!(count(&expanded, inline_always.clone()), 0);
+
+        let expanded = expand_reborrow(syn::parse_quote! {
+            #[Const(ViewRef)]
+            #[reborrow(inline_always)]
+            struct ViewMut<'a> {
+                #[reborrow]
+                x: &'a mut i32,
+            }
+        });
+        assert_eq!(count(&expanded, inline.clone()), 0);
+        assert!(count(&expanded, inline_always.clone()) > 0);
+
+        let expanded = expand_reborrow_copy(syn::parse_quote! {
+            #[reborrow(inline_always, snapshot)]
+            struct ViewRef<'a> {
+                x: &'a i32,
+            }
+        });
+        assert_eq!(count(&expanded, inline), 0);
+        assert!(count(&expanded, inline_always) > 0);
     }
 }
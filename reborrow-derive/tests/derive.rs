@@ -0,0 +1,1138 @@
+use reborrow::{IntoConst, Reborrow, ReborrowCopyTraits, ReborrowMut, ReborrowTraits};
+
+#[derive(ReborrowCopyTraits)]
+struct I32Ref<'a> {
+    i: &'a i32,
+}
+
+#[test]
+fn rename() {
+    #[derive(ReborrowTraits)]
+    #[Const(I32Ref)]
+    #[reborrow(rename = "borrow_mut")]
+    struct I32RefMut<'a> {
+        #[reborrow]
+        i: &'a mut i32,
+    }
+
+    fn takes_view(v: I32RefMut<'_>) {
+        *v.i += 1;
+    }
+
+    let mut x = 0;
+    let mut view = I32RefMut { i: &mut x };
+    takes_view(view.borrow_mut());
+    takes_view(view.borrow_mut());
+    assert_eq!(*view.rb().i, 2);
+}
+
+#[test]
+fn enums() {
+    #[derive(ReborrowCopyTraits)]
+    enum ValueRef<'a> {
+        Pair { a: &'a i32, b: &'a i32 },
+        Single(u8, &'a i32),
+        Empty,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(ValueRef)]
+    enum ValueMut<'a> {
+        Pair {
+            #[reborrow]
+            a: &'a mut i32,
+            #[reborrow]
+            b: &'a mut i32,
+        },
+        Single(u8, #[reborrow] &'a mut i32),
+        Empty,
+    }
+
+    fn incr(v: ValueMut<'_>) {
+        match v {
+            ValueMut::Pair { a, b } => {
+                *a += 1;
+                *b += 2;
+            }
+            ValueMut::Single(n, x) => *x += i32::from(n),
+            ValueMut::Empty => {}
+        }
+    }
+
+    fn sum(v: ValueRef<'_>) -> i32 {
+        match v {
+            ValueRef::Pair { a, b } => a + b,
+            ValueRef::Single(n, x) => *x + i32::from(n),
+            ValueRef::Empty => 0,
+        }
+    }
+
+    let (mut a, mut b, mut c) = (0, 0, 0);
+
+    let mut pair = ValueMut::Pair {
+        a: &mut a,
+        b: &mut b,
+    };
+    incr(pair.rb_mut());
+    incr(pair.rb_mut());
+    assert_eq!(sum(pair.rb()), 6);
+    assert_eq!(sum(pair.into_const()), 6);
+
+    let mut single = ValueMut::Single(5, &mut c);
+    incr(single.rb_mut());
+    incr(single.rb_mut());
+    assert_eq!(sum(single.into_const()), 15);
+
+    let mut empty = ValueMut::Empty;
+    incr(empty.rb_mut());
+    assert_eq!(sum(empty.rb()), 0);
+    assert_eq!(sum(empty.into_const()), 0);
+}
+
+#[test]
+fn copy_const() {
+    struct PairRef<'a> {
+        a: &'a i32,
+        b: &'a i32,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(PairRef)]
+    #[reborrow(copy_const)]
+    struct PairMut<'a> {
+        #[reborrow]
+        a: &'a mut i32,
+        #[reborrow]
+        b: &'a mut i32,
+    }
+
+    fn sum(p: PairRef<'_>) -> i32 {
+        p.a + p.b
+    }
+
+    let (mut a, mut b) = (1, 2);
+    let mut view = PairMut {
+        a: &mut a,
+        b: &mut b,
+    };
+    *view.rb_mut().a += 1;
+    let r = view.rb();
+    let copy = r;
+    assert_eq!(sum(r), 4);
+    assert_eq!(sum(copy.rb()), 4);
+    assert_eq!(sum(copy.into_const()), 4);
+    let r = view.into_const();
+    assert_eq!(sum(r), sum(r));
+}
+
+#[test]
+fn const_spellings() {
+    #[derive(ReborrowTraits)]
+    #[Const(I32Ref)]
+    struct Parenthesized<'a> {
+        #[reborrow]
+        i: &'a mut i32,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const = "I32Ref"]
+    struct Quoted<'a> {
+        #[reborrow]
+        i: &'a mut i32,
+    }
+
+    let mut x = 1;
+    let mut parenthesized = Parenthesized { i: &mut x };
+    *parenthesized.rb_mut().i += 1;
+    assert_eq!(*parenthesized.rb().i, 2);
+
+    let mut quoted = Quoted { i: &mut x };
+    *quoted.rb_mut().i += 1;
+    assert_eq!(*quoted.into_const().i, 3);
+}
+
+#[test]
+fn field_tuple() {
+    #[derive(ReborrowCopyTraits)]
+    struct ViewRef<'a> {
+        len: usize,
+        data: &'a [f64],
+        norm: &'a f64,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(ViewRef)]
+    #[reborrow(field_tuple)]
+    struct ViewMut<'a> {
+        len: usize,
+        #[reborrow]
+        data: &'a mut [f64],
+        #[reborrow]
+        norm: &'a mut f64,
+    }
+
+    let mut data = [1.0, 2.0];
+    let mut norm = 0.0;
+    let mut view = ViewMut {
+        len: 2,
+        data: &mut data,
+        norm: &mut norm,
+    };
+
+    let (data, norm) = view.reborrow_fields();
+    *norm = data.iter().sum();
+    let (data, norm) = view.reborrow_fields();
+    data[0] = *norm;
+
+    let view = view.into_const();
+    assert_eq!(view.len, 2);
+    assert_eq!(view.data, [3.0, 2.0]);
+    assert_eq!(*view.norm, 3.0);
+}
+
+#[test]
+fn copied_plain_fields() {
+    #[derive(ReborrowCopyTraits)]
+    struct Buf<'a> {
+        ptr: &'a [u8],
+        len: usize,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(Buf)]
+    struct BufMut<'a> {
+        #[reborrow]
+        ptr: &'a mut [u8],
+        len: usize,
+    }
+
+    fn fill(buf: BufMut<'_>, value: u8) -> usize {
+        buf.ptr[..buf.len].fill(value);
+        buf.len
+    }
+
+    let mut storage = [0u8; 4];
+    let mut buf = BufMut {
+        ptr: &mut storage,
+        len: 2,
+    };
+    assert_eq!(fill(buf.rb_mut(), 1), 2);
+    buf.len = 3;
+    assert_eq!(fill(buf.rb_mut(), 2), 3);
+
+    let buf = buf.rb();
+    assert_eq!(buf.len, 3);
+    assert_eq!(buf.ptr, [2, 2, 2, 0]);
+}
+
+#[test]
+fn transparent() {
+    #[derive(ReborrowTraits)]
+    #[reborrow(transparent)]
+    #[repr(transparent)]
+    struct Slice<'a>(&'a mut [f64]);
+
+    fn double(s: Slice<'_>) {
+        for x in s.0 {
+            *x *= 2.0;
+        }
+    }
+
+    let mut data = [1.0, 2.0];
+    let mut s = Slice(&mut data);
+    double(s.rb_mut());
+    double(s.rb_mut());
+    let r: &[f64] = s.rb();
+    assert_eq!(r, [4.0, 8.0]);
+    let r: &[f64] = s.into_const();
+    assert_eq!(r, [4.0, 8.0]);
+}
+
+#[test]
+fn const_ctor() {
+    mod other {
+        #[derive(Copy, Clone)]
+        #[non_exhaustive]
+        pub struct PairRef<'a> {
+            pub a: &'a i32,
+            pub b: &'a i32,
+        }
+
+        impl<'a> PairRef<'a> {
+            pub fn new(a: &'a i32, b: &'a i32) -> Self {
+                PairRef { a, b }
+            }
+        }
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(other::PairRef)]
+    #[reborrow(const_ctor = "other::PairRef::new")]
+    struct PairMut<'a> {
+        #[reborrow]
+        a: &'a mut i32,
+        #[reborrow]
+        b: &'a mut i32,
+    }
+
+    let (mut a, mut b) = (1, 2);
+    let mut view = PairMut {
+        a: &mut a,
+        b: &mut b,
+    };
+    *view.rb_mut().b += 1;
+    let r = view.rb();
+    assert_eq!((*r.a, *r.b), (1, 3));
+    let r = view.into_const();
+    assert_eq!((*r.a, *r.b), (1, 3));
+}
+
+#[test]
+fn reference_hiding_alias() {
+    type Ref<'a> = &'a i32;
+    type RefMut<'a> = &'a mut i32;
+
+    #[derive(ReborrowCopyTraits)]
+    struct AliasRef<'a> {
+        x: Ref<'a>,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(AliasRef)]
+    struct AliasMut<'a> {
+        #[reborrow]
+        x: RefMut<'a>,
+    }
+
+    let mut x = 0;
+    let mut view = AliasMut { x: &mut x };
+    *view.rb_mut().x += 1;
+    *view.rb_mut().x += 1;
+    assert_eq!(*view.rb().x, 2);
+    assert_eq!(*view.into_const().x, 2);
+}
+
+#[test]
+fn rb_through_shared_ref() {
+    #[derive(ReborrowTraits)]
+    #[Const(I32Ref)]
+    struct I32RefMut<'a> {
+        #[reborrow]
+        i: &'a mut i32,
+    }
+
+    fn read(view: &I32RefMut<'_>) -> i32 {
+        // method call syntax resolves to the impl on `I32RefMut`, not the one on `&I32RefMut`
+        let r: I32Ref<'_> = view.rb();
+        *r.i
+    }
+
+    let mut x = 1;
+    let view = I32RefMut { i: &mut x };
+    assert_eq!(read(&view), 1);
+
+    let shared = &view;
+    let r: &I32RefMut<'_> = Reborrow::rb(&shared);
+    assert_eq!(*r.i, 1);
+}
+
+#[test]
+fn mixed_mut_and_shared_fields() {
+    #[derive(ReborrowCopyTraits)]
+    struct MixedRef<'a> {
+        a: &'a i32,
+        b: &'a [i32],
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(MixedRef)]
+    struct MixedMut<'a> {
+        #[reborrow]
+        a: &'a mut i32,
+        #[reborrow]
+        b: &'a [i32],
+    }
+
+    fn add_sum(v: MixedMut<'_>) {
+        *v.a += v.b.iter().sum::<i32>();
+    }
+
+    let mut x = 0;
+    let y = [1, 2];
+    let mut view = MixedMut { a: &mut x, b: &y };
+    add_sum(view.rb_mut());
+    add_sum(view.rb_mut());
+    let r = view.rb();
+    assert_eq!((*r.a, r.b), (6, &[1, 2][..]));
+    assert_eq!(*view.into_const().a, 6);
+}
+
+#[test]
+fn default_type_param() {
+    #[derive(ReborrowCopyTraits)]
+    struct ViewRef<'a, T = u8> {
+        p: &'a [T],
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(ViewRef)]
+    struct ViewMut<'a, T = u8> {
+        #[reborrow]
+        p: &'a mut [T],
+    }
+
+    fn fill(v: ViewMut<'_>, value: u8) {
+        v.p.fill(value);
+    }
+
+    let mut buf = [0u8; 2];
+    let mut view: ViewMut<'_> = ViewMut { p: &mut buf };
+    fill(view.rb_mut(), 1);
+    let r: ViewRef<'_> = view.rb();
+    assert_eq!(r.p, [1, 1]);
+
+    let mut buf = [0i32; 2];
+    let mut view = ViewMut { p: &mut buf };
+    view.rb_mut().p[0] = 2;
+    assert_eq!(view.into_const().p, [2, 0]);
+}
+
+#[test]
+fn dyn_any_send_sync_field() {
+    use std::any::Any;
+
+    #[derive(ReborrowCopyTraits)]
+    struct EntryRef<'a> {
+        name: &'a str,
+        value: &'a (dyn Any + Send + Sync),
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(EntryRef)]
+    struct EntryMut<'a> {
+        name: &'a str,
+        #[reborrow]
+        value: &'a mut (dyn Any + Send + Sync),
+    }
+
+    fn incr(e: EntryMut<'_>) {
+        *e.value.downcast_mut::<i32>().unwrap() += 1;
+    }
+
+    let mut x = 0i32;
+    let mut entry = EntryMut {
+        name: "x",
+        value: &mut x,
+    };
+    incr(entry.rb_mut());
+    incr(entry.rb_mut());
+    let r = entry.into_const();
+    assert_eq!((r.name, r.value.downcast_ref::<i32>()), ("x", Some(&2)));
+}
+
+#[test]
+fn recursive_structure() {
+    // a boxed view can't be reborrowed to a boxed view, so the view borrows the owned nodes
+    struct Node {
+        val: i32,
+        next: Option<Box<Node>>,
+    }
+
+    #[derive(ReborrowCopyTraits)]
+    struct ListRef<'a> {
+        head: Option<&'a Node>,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(ListRef)]
+    struct ListMut<'a> {
+        #[reborrow]
+        head: Option<&'a mut Node>,
+    }
+
+    fn incr(list: ListMut<'_>) {
+        let mut node = list.head;
+        while let Some(n) = node {
+            n.val += 1;
+            node = n.next.as_deref_mut();
+        }
+    }
+
+    fn sum(list: ListRef<'_>) -> i32 {
+        let mut node = list.head;
+        let mut sum = 0;
+        while let Some(n) = node {
+            sum += n.val;
+            node = n.next.as_deref();
+        }
+        sum
+    }
+
+    let mut head = Node {
+        val: 1,
+        next: Some(Box::new(Node { val: 2, next: None })),
+    };
+    let mut list = ListMut {
+        head: Some(&mut head),
+    };
+    incr(list.rb_mut());
+    incr(list.rb_mut());
+    assert_eq!(sum(list.rb()), 7);
+    assert_eq!(sum(list.into_const()), 7);
+}
+
+#[test]
+fn generic_tuple_struct() {
+    #[derive(ReborrowCopyTraits)]
+    struct PairRef<'a, T>(&'a [T], usize);
+
+    #[derive(ReborrowTraits)]
+    #[Const(PairRef)]
+    struct PairMut<'a, T>(#[reborrow] &'a mut [T], usize);
+
+    let mut x = [1, 2];
+    let mut view = PairMut(&mut x, 1);
+    let r = view.rb_mut();
+    r.0[r.1] += 1;
+    let r = view.rb();
+    assert_eq!((r.0, r.1), (&[1, 3][..], 1));
+}
+
+#[test]
+fn manually_drop_field() {
+    use std::mem::ManuallyDrop;
+
+    #[derive(ReborrowCopyTraits)]
+    struct WrapRef<'a> {
+        x: ManuallyDrop<&'a i32>,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(WrapRef)]
+    struct WrapMut<'a> {
+        #[reborrow]
+        x: ManuallyDrop<&'a mut i32>,
+    }
+
+    fn incr(mut v: WrapMut<'_>) {
+        **v.x += 1;
+    }
+
+    let mut x = 0;
+    let mut view = WrapMut {
+        x: ManuallyDrop::new(&mut x),
+    };
+    incr(view.rb_mut());
+    incr(view.rb_mut());
+    assert_eq!(**view.rb().x, 2);
+    assert_eq!(**view.into_const().x, 2);
+}
+
+#[test]
+fn field_with() {
+    mod halves {
+        // reborrows only the first half of the slice
+        pub fn rb_mut<'s>(x: &'s mut &mut [i32]) -> &'s mut [i32] {
+            let len = x.len();
+            &mut x[..len / 2]
+        }
+
+        pub fn rb<'s>(x: &'s &mut [i32]) -> &'s [i32] {
+            &x[..x.len() / 2]
+        }
+    }
+
+    #[derive(ReborrowCopyTraits)]
+    struct HalfRef<'a> {
+        data: &'a [i32],
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(HalfRef)]
+    struct HalfMut<'a> {
+        #[reborrow(with = "halves::rb_mut", with_const = "halves::rb")]
+        data: &'a mut [i32],
+    }
+
+    let mut x = [1, 2, 3, 4];
+    let mut view = HalfMut { data: &mut x };
+    view.rb_mut().data.fill(0);
+    assert_eq!(view.rb_mut().data.len(), 2);
+    assert_eq!(view.rb().data, [0, 0]);
+    assert_eq!(view.into_const().data, [0, 0, 3, 4]);
+}
+
+#[test]
+fn const_expr_where_clause() {
+    #[derive(ReborrowCopyTraits)]
+    struct BlockRef<'a, const N: usize>
+    where
+        [(); N]: Sized,
+    {
+        p: &'a [u8],
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(BlockRef)]
+    struct BlockMut<'a, const N: usize>
+    where
+        [(); N]: Sized,
+    {
+        #[reborrow]
+        p: &'a mut [u8],
+    }
+
+    fn fill<const N: usize>(v: BlockMut<'_, N>)
+    where
+        [(); N]: Sized,
+    {
+        v.p[..N].fill(1);
+    }
+
+    let mut x = [0u8; 4];
+    let mut view = BlockMut::<2> { p: &mut x };
+    fill(view.rb_mut());
+    fill(view.rb_mut());
+    assert_eq!(view.rb().p, [1, 1, 0, 0]);
+    assert_eq!(view.into_const().p, [1, 1, 0, 0]);
+}
+
+#[test]
+fn pinned_field() {
+    use std::marker::PhantomPinned;
+    use std::pin::{pin, Pin};
+
+    struct Counter {
+        count: i32,
+        _pinned: PhantomPinned,
+    }
+
+    #[derive(ReborrowCopyTraits)]
+    struct CounterRef<'a> {
+        counter: Pin<&'a Counter>,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(CounterRef)]
+    struct CounterMut<'a> {
+        #[reborrow]
+        counter: Pin<&'a mut Counter>,
+    }
+
+    fn incr(v: CounterMut<'_>) {
+        // SAFETY: `count` isn't structurally pinned
+        unsafe { v.counter.get_unchecked_mut().count += 1 };
+    }
+
+    let counter = pin!(Counter {
+        count: 0,
+        _pinned: PhantomPinned,
+    });
+    let mut view = CounterMut { counter };
+    incr(view.rb_mut());
+    incr(view.rb_mut());
+    assert_eq!(view.rb().counter.count, 2);
+    assert_eq!(view.into_const().counter.count, 2);
+}
+
+#[test]
+fn reexported_paths() {
+    // the derives are usable through their re-exports in the main crate
+    #[derive(reborrow::ReborrowCopyTraits)]
+    struct PathRef<'a> {
+        x: &'a i32,
+    }
+
+    #[derive(reborrow::ReborrowTraits)]
+    #[Const(PathRef)]
+    struct PathMut<'a> {
+        #[reborrow]
+        x: &'a mut i32,
+    }
+
+    let mut x = 0;
+    let mut view = PathMut { x: &mut x };
+    *view.rb_mut().x += 1;
+    assert_eq!(*view.rb().x, 1);
+}
+
+#[test]
+fn dyn_with_lifetime_field() {
+    trait Handler {
+        fn handle(&mut self, x: i32);
+        fn total(&self) -> i32;
+    }
+
+    struct Sum<'a>(&'a mut i32);
+
+    impl Handler for Sum<'_> {
+        fn handle(&mut self, x: i32) {
+            *self.0 += x;
+        }
+        fn total(&self) -> i32 {
+            *self.0
+        }
+    }
+
+    #[derive(ReborrowCopyTraits)]
+    struct HandlerRef<'a> {
+        h: &'a (dyn Handler + 'a),
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(HandlerRef)]
+    struct HandlerMut<'a> {
+        #[reborrow]
+        h: &'a mut (dyn Handler + 'a),
+    }
+
+    fn send(v: HandlerMut<'_>, x: i32) {
+        v.h.handle(x);
+    }
+
+    let mut total = 0;
+    let mut sum = Sum(&mut total);
+    let mut view = HandlerMut { h: &mut sum };
+    send(view.rb_mut(), 1);
+    send(view.rb_mut(), 2);
+    assert_eq!(view.rb().h.total(), 3);
+    assert_eq!(view.into_const().h.total(), 3);
+}
+
+#[test]
+fn snapshot() {
+    #[derive(ReborrowCopyTraits)]
+    #[reborrow(snapshot)]
+    struct CursorRef<'a> {
+        data: &'a [i32],
+        pos: usize,
+    }
+
+    let data = [1, 2, 3];
+    let mut cursor = CursorRef {
+        data: &data,
+        pos: 0,
+    };
+    let snapshot = cursor.snapshot();
+    cursor.pos += 2;
+    assert_eq!(snapshot.data[snapshot.pos], 1);
+    assert_eq!(cursor.data[cursor.pos], 3);
+}
+
+#[test]
+fn dyn_field_into_const() {
+    use std::fmt::Write;
+
+    #[derive(ReborrowCopyTraits)]
+    struct SinkRef<'a> {
+        out: &'a dyn std::fmt::Debug,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(SinkRef)]
+    struct SinkMut<'a> {
+        #[reborrow]
+        out: &'a mut dyn std::fmt::Debug,
+    }
+
+    let mut x = 1;
+    let view = SinkMut { out: &mut x };
+    let r: SinkRef<'_> = view.into_const();
+    let mut s = String::new();
+    write!(s, "{:?}", r.out).unwrap();
+    assert_eq!(s, "1");
+}
+
+#[test]
+fn drop_order() {
+    use std::cell::RefCell;
+
+    #[derive(ReborrowCopyTraits)]
+    struct LogRef<'a> {
+        name: &'static str,
+        log: &'a RefCell<Vec<&'static str>>,
+        x: &'a i32,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(LogRef)]
+    #[reborrow(no_into_const)]
+    struct LogMut<'a> {
+        name: &'static str,
+        log: &'a RefCell<Vec<&'static str>>,
+        #[reborrow]
+        x: &'a mut i32,
+    }
+
+    impl Drop for LogMut<'_> {
+        fn drop(&mut self) {
+            *self.x += 1;
+            self.log.borrow_mut().push(self.name);
+        }
+    }
+
+    let log = RefCell::new(Vec::new());
+    let mut x = 0;
+    {
+        let mut view = LogMut {
+            name: "original",
+            log: &log,
+            x: &mut x,
+        };
+        {
+            let mut r = view.rb_mut();
+            r.name = "reborrow";
+        }
+        let r = view.rb();
+        assert_eq!((r.name, r.log.borrow().len(), *r.x), ("original", 1, 1));
+    }
+    assert_eq!(x, 2);
+    assert_eq!(*log.borrow(), ["reborrow", "original"]);
+}
+
+#[test]
+fn fieldless_copy_views() {
+    #[derive(ReborrowCopyTraits, Debug, PartialEq)]
+    struct Marker;
+
+    #[derive(ReborrowCopyTraits, Debug, PartialEq)]
+    struct Empty {}
+
+    let mut marker = Marker;
+    assert_eq!(marker.rb_mut(), Marker);
+    assert_eq!(marker.rb(), Marker);
+    assert_eq!(marker.into_const(), Marker);
+
+    let mut empty = Empty {};
+    assert_eq!(empty.rb_mut(), Empty {});
+    assert_eq!(empty.rb(), Empty {});
+    assert_eq!(empty.into_const(), Empty {});
+}
+
+#[test]
+fn inline_always() {
+    #[derive(ReborrowCopyTraits)]
+    #[reborrow(inline_always, snapshot)]
+    struct RowRef<'a> {
+        row: &'a [f64],
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(RowRef)]
+    #[reborrow(inline_always, rename = "row_mut")]
+    struct RowMut<'a> {
+        #[reborrow]
+        row: &'a mut [f64],
+    }
+
+    let mut x = [0.0; 4];
+    let mut view = RowMut { row: &mut x };
+    for i in 0..4 {
+        view.row_mut().row[i] = i as f64;
+    }
+    let r = view.rb().snapshot();
+    assert_eq!(r.row, [0.0, 1.0, 2.0, 3.0]);
+}
+
+#[test]
+fn function_local_items() {
+    mod views {
+        use reborrow::ReborrowCopyTraits;
+
+        #[derive(ReborrowCopyTraits)]
+        pub struct LocalRef<'a> {
+            pub x: &'a i32,
+        }
+    }
+
+    fn inner() -> i32 {
+        #[derive(ReborrowTraits)]
+        #[Const(views::LocalRef)]
+        struct LocalMut<'a> {
+            #[reborrow]
+            x: &'a mut i32,
+        }
+
+        let mut x = 0;
+        let mut view = LocalMut { x: &mut x };
+        *view.rb_mut().x += 1;
+        *view.rb_mut().x += 1;
+        *view.into_const().x
+    }
+
+    assert_eq!(inner(), 2);
+}
+
+#[test]
+fn tuple_and_array_fields() {
+    // `Box<T>` reborrows to a reference, not to a box, so it can't be a field of a derived view
+    #[derive(ReborrowCopyTraits)]
+    struct MixedRef<'a> {
+        pair: (&'a i32, Option<&'a u8>),
+        arr: [&'a i32; 2],
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(MixedRef)]
+    struct MixedMut<'a> {
+        #[reborrow]
+        pair: (&'a mut i32, Option<&'a mut u8>),
+        #[reborrow]
+        arr: [&'a mut i32; 2],
+    }
+
+    fn incr(v: MixedMut<'_>) {
+        *v.pair.0 += 1;
+        *v.pair.1.unwrap() += 1;
+        for x in v.arr {
+            *x += 1;
+        }
+    }
+
+    let (mut a, mut b, mut c, mut d) = (0, 0, 0, 0);
+    let mut view = MixedMut {
+        pair: (&mut a, Some(&mut b)),
+        arr: [&mut c, &mut d],
+    };
+    incr(view.rb_mut());
+    incr(view.rb_mut());
+    let r = view.rb();
+    assert_eq!((*r.pair.0, r.pair.1, r.arr), (2, Some(&2), [&2, &2]));
+    let r = view.into_const();
+    assert_eq!((*r.pair.0, r.pair.1, r.arr), (2, Some(&2), [&2, &2]));
+}
+
+#[test]
+fn associated_const_array_len() {
+    trait Block {
+        const N: usize;
+    }
+
+    struct Pair;
+
+    impl Block for Pair {
+        const N: usize = 2;
+    }
+
+    // array lengths depending on a generic parameter, like `[u8; T::N]`, need
+    // `generic_const_exprs`, so the length must be a concrete associated const
+    #[derive(ReborrowCopyTraits)]
+    struct BlockRef<'a> {
+        buf: &'a [u8; <Pair as Block>::N],
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(BlockRef)]
+    struct BlockMut<'a> {
+        #[reborrow]
+        buf: &'a mut [u8; <Pair as Block>::N],
+    }
+
+    let mut x = [0u8; 2];
+    let mut view = BlockMut { buf: &mut x };
+    view.rb_mut().buf[0] += 1;
+    view.rb_mut().buf[1] += 2;
+    assert_eq!(view.rb().buf, &[1, 2]);
+    assert_eq!(view.into_const().buf, &[1, 2]);
+}
+
+#[test]
+fn raw_identifiers() {
+    #[derive(ReborrowCopyTraits)]
+    struct KindRef<'a> {
+        r#type: &'a i32,
+        r#ref: bool,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(KindRef)]
+    #[reborrow(field_tuple)]
+    struct KindMut<'a> {
+        #[reborrow]
+        r#type: &'a mut i32,
+        r#ref: bool,
+    }
+
+    let mut x = 0;
+    let mut view = KindMut {
+        r#type: &mut x,
+        r#ref: true,
+    };
+    *view.rb_mut().r#type += 1;
+    *view.rb_mut().r#type += 1;
+    let (t,) = view.reborrow_fields();
+    *t += 1;
+    let r = view.into_const();
+    assert_eq!((*r.r#type, r.r#ref), (3, true));
+}
+
+mod view {
+    use reborrow::*;
+
+    #[derive(ReborrowCopyTraits)]
+    pub struct CounterRef<'a> {
+        pub count: &'a u32,
+        pub step: u32,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(CounterRef)]
+    pub struct CounterMut<'a> {
+        #[reborrow]
+        count: &'a mut u32,
+        step: u32,
+    }
+
+    impl<'a> CounterMut<'a> {
+        pub fn new(count: &'a mut u32, step: u32) -> Self {
+            Self { count, step }
+        }
+
+        pub fn tick(&mut self) {
+            *self.count += self.step;
+        }
+    }
+}
+
+#[test]
+fn private_mut_fields() {
+    let mut x = 0;
+    let mut view = view::CounterMut::new(&mut x, 2);
+    view.rb_mut().tick();
+    view.rb_mut().tick();
+    assert_eq!(*view.rb().count, 4);
+    let r = view.into_const();
+    assert_eq!((*r.count, r.step), (4, 2));
+}
+
+mod reexport {
+    pub mod traits {
+        pub use reborrow::{AsGeneralizedMut, AsGeneralizedRef, IntoConst, Reborrow, ReborrowMut};
+    }
+}
+
+#[test]
+fn crate_path() {
+    use reexport::traits::{IntoConst, Reborrow, ReborrowMut};
+
+    #[derive(ReborrowCopyTraits)]
+    #[reborrow(crate = "reexport::traits")]
+    struct SumRef<'a> {
+        sum: &'a i64,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(SumRef)]
+    #[reborrow(crate = "reexport::traits")]
+    struct SumMut<'a> {
+        #[reborrow]
+        sum: &'a mut i64,
+    }
+
+    let mut x = 0;
+    let mut view = SumMut { sum: &mut x };
+    *view.rb_mut().sum += 2;
+    *view.rb_mut().sum += 3;
+    let r = view.rb();
+    assert_eq!(*r.rb().sum, 5);
+    assert_eq!(*view.into_const().sum, 5);
+}
+
+#[test]
+fn long_names() {
+    #[derive(ReborrowCopyTraits)]
+    struct BufRef<'a> {
+        buf: &'a [u8],
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(BufRef)]
+    #[reborrow(long_names)]
+    struct BufMut<'a> {
+        #[reborrow]
+        buf: &'a mut [u8],
+    }
+
+    let mut x = [0u8; 2];
+    let mut view = BufMut { buf: &mut x };
+    view.reborrow_mut().buf[0] = 1;
+    view.reborrow_mut().buf[1] = 2;
+    assert_eq!(view.reborrow().buf, &[1, 2]);
+    assert_eq!(view.rb().buf, &[1, 2]);
+}
+
+#[test]
+fn tagged_view() {
+    use core::mem::{discriminant, Discriminant};
+
+    enum Value {
+        Int(i64),
+        Float(f64),
+    }
+
+    #[derive(ReborrowCopyTraits)]
+    struct SlotRef<'a> {
+        tag: Discriminant<Value>,
+        payload: &'a Value,
+    }
+
+    #[derive(ReborrowTraits)]
+    #[Const(SlotRef)]
+    struct SlotMut<'a> {
+        tag: Discriminant<Value>,
+        #[reborrow]
+        payload: &'a mut Value,
+    }
+
+    impl SlotMut<'_> {
+        fn negate(self) {
+            if self.tag == discriminant(&Value::Int(0)) {
+                if let Value::Int(x) = self.payload {
+                    *x = -*x;
+                }
+            } else if let Value::Float(x) = self.payload {
+                *x = -*x;
+            }
+        }
+    }
+
+    let mut value = Value::Int(3);
+    let mut view = SlotMut {
+        tag: discriminant(&value),
+        payload: &mut value,
+    };
+    view.rb_mut().negate();
+    view.rb_mut().negate();
+    view.rb_mut().negate();
+    let r = view.into_const();
+    assert!(r.tag == discriminant(&Value::Int(0)));
+    assert!(matches!(r.payload, Value::Int(-3)));
+    assert!(r.tag != discriminant(&Value::Float(0.0)));
+}
+
+#[test]
+fn mut_only() {
+    #[derive(ReborrowTraits)]
+    #[reborrow(mut_only, long_names)]
+    struct SinkMut<'a> {
+        #[reborrow]
+        out: &'a mut Vec<u8>,
+        fill: u8,
+    }
+
+    impl SinkMut<'_> {
+        fn push(self) {
+            self.out.push(self.fill);
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut view = SinkMut {
+        out: &mut out,
+        fill: 7,
+    };
+    view.rb_mut().push();
+    view.reborrow_mut().push();
+    view.rb_mut().push();
+    assert_eq!(out, [7, 7, 7]);
+}
@@ -0,0 +1,19 @@
+use reborrow::{ReborrowCopyTraits, ReborrowTraits};
+
+#[derive(ReborrowCopyTraits)]
+struct PairRef<'a> {
+    a: &'a i32,
+    b: &'a i32,
+}
+
+// the const variant must have the same generic parameters as the mutable one
+#[derive(ReborrowTraits)]
+#[Const(PairRef)]
+struct PairMut<'a, 'b> {
+    #[reborrow]
+    a: &'a mut i32,
+    #[reborrow]
+    b: &'b mut i32,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use reborrow::{ReborrowCopyTraits, ReborrowTraits};
+
+#[derive(ReborrowCopyTraits)]
+struct I32Ref<'a> {
+    i: &'a i32,
+}
+
+#[derive(ReborrowTraits)]
+#[Const(I32Ref, extra)]
+struct I32RefMut<'a> {
+    #[reborrow]
+    i: &'a mut i32,
+}
+
+fn main() {}
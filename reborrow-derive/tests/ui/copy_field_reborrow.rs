@@ -0,0 +1,10 @@
+use reborrow::ReborrowCopyTraits;
+
+// field attributes only make sense on the mutable variant, and would otherwise be silently ignored
+#[derive(Clone, Copy, ReborrowCopyTraits)]
+struct ViewRef<'a> {
+    #[reborrow(with = core::convert::identity)]
+    data: &'a [u8],
+}
+
+fn main() {}
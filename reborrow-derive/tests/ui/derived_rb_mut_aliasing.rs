@@ -0,0 +1,22 @@
+use reborrow::{ReborrowCopyTraits, ReborrowMut, ReborrowTraits};
+
+#[derive(ReborrowCopyTraits)]
+struct I32Ref<'a> {
+    i: &'a i32,
+}
+
+#[derive(ReborrowTraits)]
+#[Const(I32Ref)]
+struct I32RefMut<'a> {
+    #[reborrow]
+    i: &'a mut i32,
+}
+
+fn main() {
+    let mut x = 0;
+    let mut view = I32RefMut { i: &mut x };
+    let first = view.rb_mut();
+    let second = view.rb_mut();
+    *first.i += 1;
+    *second.i += 1;
+}
@@ -0,0 +1,30 @@
+use reborrow::{ReborrowCopyTraits, ReborrowMut, ReborrowTraits};
+
+#[derive(ReborrowCopyTraits)]
+struct PairRef<'a, 'b> {
+    a: &'a i32,
+    b: &'b i32,
+}
+
+#[derive(ReborrowTraits)]
+#[Const(PairRef)]
+struct PairMut<'a, 'b> {
+    #[reborrow]
+    a: &'a mut i32,
+    #[reborrow]
+    b: &'b mut i32,
+}
+
+// the reborrowed view only lives as long as the borrow of `view`, and not as long as `'a`
+fn extend<'a>(view: &mut PairMut<'a, 'a>) -> PairMut<'a, 'a> {
+    view.rb_mut()
+}
+
+fn main() {
+    let (mut a, mut b) = (0, 0);
+    let mut view = PairMut {
+        a: &mut a,
+        b: &mut b,
+    };
+    let _ = extend(&mut view);
+}
@@ -0,0 +1,30 @@
+use reborrow::{Reborrow, ReborrowCopyTraits, ReborrowTraits};
+
+#[derive(ReborrowCopyTraits)]
+struct PairRef<'a, 'b> {
+    a: &'a i32,
+    b: &'b i32,
+}
+
+#[derive(ReborrowTraits)]
+#[Const(PairRef)]
+struct PairMut<'a, 'b> {
+    #[reborrow]
+    a: &'a mut i32,
+    #[reborrow]
+    b: &'b mut i32,
+}
+
+// the const view only lives as long as the borrow of `view`, and not as long as `'b`
+fn extend<'a, 'b>(view: &'a PairMut<'a, 'b>) -> PairRef<'b, 'b> {
+    view.rb()
+}
+
+fn main() {
+    let (mut a, mut b) = (0, 0);
+    let view = PairMut {
+        a: &mut a,
+        b: &mut b,
+    };
+    let _ = extend(&view);
+}
@@ -0,0 +1,18 @@
+use reborrow::{ReborrowCopyTraits, ReborrowTraits};
+
+#[derive(ReborrowCopyTraits)]
+struct ViewRef<'a> {
+    len: usize,
+    data: &'a u64,
+}
+
+// `data` doesn't reborrow to the type of its counterpart in `ViewRef`
+#[derive(ReborrowTraits)]
+#[Const(ViewRef)]
+struct ViewMut<'a> {
+    len: usize,
+    #[reborrow]
+    data: &'a mut i32,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use reborrow::{Reborrow, ReborrowMut, ReborrowTraits};
+
+// `mut_only` views can't be reborrowed immutably
+#[derive(ReborrowTraits)]
+#[reborrow(mut_only)]
+struct SinkMut<'a> {
+    #[reborrow]
+    out: &'a mut Vec<u8>,
+}
+
+fn main() {
+    let mut out = Vec::new();
+    let mut view = SinkMut { out: &mut out };
+    view.rb_mut().out.push(0);
+    let _ = view.rb();
+}
@@ -0,0 +1,20 @@
+use reborrow::{ReborrowCopyTraits, ReborrowTraits};
+
+#[derive(ReborrowCopyTraits)]
+struct ViewRef<'a, L: Copy> {
+    len: L,
+    data: &'a [u8],
+}
+
+// `len` is a plain `Copy` field, and must not be marked `#[reborrow]`. it's generic so that the
+// error doesn't list the types implementing the reborrow traits.
+#[derive(ReborrowTraits)]
+#[Const(ViewRef)]
+struct ViewMut<'a, L: Copy> {
+    #[reborrow]
+    len: L,
+    #[reborrow]
+    data: &'a mut [u8],
+}
+
+fn main() {}
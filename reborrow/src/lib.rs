@@ -53,7 +53,7 @@
 //! drop(o); // can still be used here
 //! ```
 //!
-//! The derive macro can be used with structs or tuple structs, on the mutable variant and
+//! The derive macro can be used with structs, tuple structs, or enums, on the mutable variant and
 //! generates the trait definitions for [`Reborrow`], [`ReborrowMut`], and [`IntoConst`].
 //!
 //! ```
@@ -87,15 +87,289 @@
 //!     #[reborrow] &'b mut i32,
 //! );
 //! ```
+//!
+//! The const variant must have the same generic parameters as the mutable variant, in the same
+//! order, since it is named with the generic arguments of the mutable variant. A const variant
+//! with fewer lifetimes is reported as taking the wrong number of lifetime arguments.
+//!
+//! The generated impls construct the const variant with a struct literal and read the fields of
+//! the mutable variant, so the fields of both must be visible where the derive is placed. Private
+//! fields work as long as both structs are defined in the same module, and the const variant can
+//! expose more of its fields than the mutable variant.
+//!
+//! Fields marked `#[reborrow]` are reborrowed through the reborrow traits, and their type must
+//! implement them. Other fields are copied into the reborrowed view, and must be `Copy`. Marking
+//! a plain `Copy` field as `#[reborrow]` is a mistake, and is reported as its type not
+//! implementing the reborrow traits. A field whose type doesn't match the one of its counterpart
+//! in the const variant is reported as a type mismatch on the field.
+//!
+//! `#[derive(ReborrowCopyTraits)]` accepts `#[reborrow(snapshot)]`, which also generates an
+//! inherent method `snapshot` returning a copy of the view with the same lifetime, as well as
+//! `#[reborrow(inline_always)]` and `#[reborrow(crate = "...")]`, described below.
+//!
+//! `#[derive(ReborrowTraits)]` also accepts the following options on the mutable variant, given as
+//! `#[reborrow(...)]`:
+//! - `rename = "name"`: also generate an inherent method `name` forwarding to `rb_mut`.
+//! - `long_names`: also generate inherent methods `reborrow` and `reborrow_mut`, forwarding to
+//!   `rb` and `rb_mut`.
+//! - `copy_const`: also generate the `ReborrowCopyTraits` impls for the const variant, so that it
+//!   doesn't need its own derive.
+//! - `field_tuple`: also generate an inherent method `reborrow_fields`, returning a tuple of the
+//!   mutably reborrowed `#[reborrow]` fields, in declaration order.
+//! - `transparent`: for views wrapping a single field, which is reborrowed regardless of
+//!   `#[reborrow]`. No const variant is specified, and `rb` and `into_const` return the ones of
+//!   the field instead.
+//! - `const_ctor = "path::to::fn"`: construct the const variant by calling the given function
+//!   with its fields in declaration order, instead of a struct literal. This is needed when the
+//!   const variant is `#[non_exhaustive]` and defined in another crate.
+//! - `inline_always`: mark the generated methods `#[inline(always)]`, instead of `#[inline]`.
+//! - `no_into_const`: don't generate the `IntoConst` impl. This is needed for views with a `Drop`
+//!   impl and mutably reborrowed fields, since these fields can't be moved out of the view.
+//!   Reborrowing such views is fine, and the reborrowed view is dropped before the original one.
+//! - `mut_only`: only generate the `ReborrowMut` and `AsGeneralizedMut` impls, for views that
+//!   shouldn't be reborrowed immutably. No const variant is specified.
+//! - `crate = "path::to::reborrow"`: refer to this crate through the given path in the generated
+//!   code, instead of `::reborrow`. This is needed when it's renamed, or only reachable through a
+//!   re-export.
+//!
+//! Fields marked `#[reborrow]` accept the following options, given as `#[reborrow(...)]`:
+//! - `with = "path::to::fn"`: reborrow the field mutably by calling the given function with a
+//!   mutable reference to it, instead of `ReborrowMut::rb_mut`.
+//! - `with_const = "path::to::fn"`: reborrow the field immutably by calling the given function
+//!   with a shared reference to it, instead of `Reborrow::rb`. `into_const` still goes through
+//!   the `IntoConst` impl of the field.
+//!
+//! No `Reborrow` impl is generated for `&MutStruct`, since it would overlap with the one for
+//! `&T`. This is rarely an issue, because method call syntax already picks the impl for the
+//! pointee: `(&view).rb()` returns the const variant. Only the fully qualified
+//! `Reborrow::rb(&&view)` returns a `&MutStruct`.
+//!
+//! Operators can be implemented on the const variant, so that they work on reborrowed views.
+//! ```
+//! use core::ops::Add;
+//! use reborrow::{Reborrow, ReborrowCopyTraits, ReborrowMut, ReborrowTraits};
+//!
+//! #[derive(ReborrowCopyTraits)]
+//! pub struct VecRef<'a> {
+//!     pub x: &'a [f64],
+//! }
+//!
+//! #[derive(ReborrowTraits)]
+//! #[Const(VecRef)]
+//! pub struct VecMut<'a> {
+//!     #[reborrow]
+//!     pub x: &'a mut [f64],
+//! }
+//!
+//! impl Add for VecRef<'_> {
+//!     type Output = Vec<f64>;
+//!
+//!     fn add(self, rhs: Self) -> Vec<f64> {
+//!         self.x.iter().zip(rhs.x).map(|(a, b)| a + b).collect()
+//!     }
+//! }
+//!
+//! let (mut a, mut b) = ([1.0, 2.0], [3.0, 4.0]);
+//! let mut a = VecMut { x: &mut a };
+//! let b = VecMut { x: &mut b };
+//! a.rb_mut().x[0] = 0.0;
+//! assert_eq!(a.rb() + b.rb(), [3.0, 6.0]);
+//! assert_eq!(a.rb() + a.rb(), [0.0, 4.0]);
+//! ```
+//!
+//! Builders holding a mutable reference can be made reborrowable, so that their consuming methods
+//! can be called from several functions without giving the builder away.
+//! ```
+//! use reborrow::{ReborrowCopyTraits, ReborrowMut, ReborrowTraits};
+//!
+//! #[derive(Default)]
+//! pub struct Command {
+//!     pub args: Vec<String>,
+//!     pub envs: Vec<(String, String)>,
+//! }
+//!
+//! #[derive(ReborrowCopyTraits)]
+//! pub struct CommandRef<'a> {
+//!     pub cmd: &'a Command,
+//! }
+//!
+//! #[derive(ReborrowTraits)]
+//! #[Const(CommandRef)]
+//! pub struct CommandBuilder<'a> {
+//!     #[reborrow]
+//!     pub cmd: &'a mut Command,
+//! }
+//!
+//! impl CommandBuilder<'_> {
+//!     pub fn arg(self, arg: &str) -> Self {
+//!         self.cmd.args.push(arg.to_string());
+//!         self
+//!     }
+//!
+//!     pub fn env(self, key: &str, value: &str) -> Self {
+//!         self.cmd.envs.push((key.to_string(), value.to_string()));
+//!         self
+//!     }
+//! }
+//!
+//! fn verbose(builder: CommandBuilder<'_>) {
+//!     builder.arg("--verbose").env("LOG", "debug");
+//! }
+//!
+//! let mut cmd = Command::default();
+//! let mut builder = CommandBuilder { cmd: &mut cmd };
+//! builder.rb_mut().arg("build");
+//! verbose(builder.rb_mut());
+//! builder.arg("--release");
+//! assert_eq!(cmd.args, ["build", "--verbose", "--release"]);
+//! assert_eq!(cmd.envs, [("LOG".to_string(), "debug".to_string())]);
+//! ```
+//!
+//! Shared fields with interior mutability, such as an operation counter behind a `&Cell`, are
+//! copied into the reborrowed view, and shared between the original and the reborrowed views.
+//! ```
+//! use std::cell::Cell;
+//! use reborrow::{Reborrow, ReborrowCopyTraits, ReborrowMut, ReborrowTraits};
+//!
+//! #[derive(ReborrowCopyTraits)]
+//! pub struct VecRef<'a> {
+//!     pub data: &'a [f64],
+//!     pub flops: &'a Cell<usize>,
+//! }
+//!
+//! #[derive(ReborrowTraits)]
+//! #[Const(VecRef)]
+//! pub struct VecMut<'a> {
+//!     #[reborrow]
+//!     pub data: &'a mut [f64],
+//!     pub flops: &'a Cell<usize>,
+//! }
+//!
+//! fn scale(v: VecMut<'_>, alpha: f64) {
+//!     v.data.iter_mut().for_each(|x| *x *= alpha);
+//!     v.flops.set(v.flops.get() + v.data.len());
+//! }
+//!
+//! fn norm2(v: VecRef<'_>) -> f64 {
+//!     v.flops.set(v.flops.get() + 2 * v.data.len());
+//!     v.data.iter().map(|x| x * x).sum()
+//! }
+//!
+//! let flops = Cell::new(0);
+//! let mut data = [1.0, 2.0];
+//! let mut v = VecMut { data: &mut data, flops: &flops };
+//! scale(v.rb_mut(), 2.0);
+//! scale(v.rb_mut(), 0.5);
+//! assert_eq!(norm2(v.rb()), 5.0);
+//! assert_eq!(flops.get(), 8);
+//! ```
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 // _Outlives: suggestion from /u/YatoRust
 // https://www.reddit.com/r/rust/comments/tjzy97/reborrow_emulating_reborrowing_for_user_types/i1nco4i/
 
 #[cfg(feature = "derive")]
 pub use reborrow_derive::{ReborrowCopyTraits, ReborrowTraits};
 
+/// Re-exports the reborrow traits, for crates implementing them on their own view types.
+///
+/// ```
+/// mod external {
+///     use reborrow::prelude::*;
+///
+///     /// A mutable view over a column-major matrix.
+///     pub struct MatViewMut<'a> {
+///         data: &'a mut [f64],
+///         nrows: usize,
+///     }
+///
+///     /// A shared view over a column-major matrix.
+///     #[derive(Copy, Clone)]
+///     pub struct MatView<'a> {
+///         data: &'a [f64],
+///         nrows: usize,
+///     }
+///
+///     impl<'a> MatViewMut<'a> {
+///         pub fn new(data: &'a mut [f64], nrows: usize) -> Self {
+///             Self { data, nrows }
+///         }
+///
+///         pub fn write(self, row: usize, col: usize, value: f64) {
+///             self.data[row + col * self.nrows] = value;
+///         }
+///     }
+///
+///     impl MatView<'_> {
+///         pub fn read(self, row: usize, col: usize) -> f64 {
+///             self.data[row + col * self.nrows]
+///         }
+///     }
+///
+///     impl<'short> ReborrowMut<'short> for MatViewMut<'_> {
+///         type Target = MatViewMut<'short>;
+///
+///         fn rb_mut(&'short mut self) -> Self::Target {
+///             MatViewMut {
+///                 data: self.data,
+///                 nrows: self.nrows,
+///             }
+///         }
+///     }
+///
+///     impl<'short> Reborrow<'short> for MatViewMut<'_> {
+///         type Target = MatView<'short>;
+///
+///         fn rb(&'short self) -> Self::Target {
+///             MatView {
+///                 data: self.data,
+///                 nrows: self.nrows,
+///             }
+///         }
+///     }
+///
+///     impl<'a> IntoConst for MatViewMut<'a> {
+///         type Target = MatView<'a>;
+///
+///         fn into_const(self) -> Self::Target {
+///             MatView {
+///                 data: self.data,
+///                 nrows: self.nrows,
+///             }
+///         }
+///     }
+/// }
+///
+/// use external::MatViewMut;
+/// use reborrow::prelude::*;
+///
+/// let mut data = [0.0; 4];
+/// let mut mat = MatViewMut::new(&mut data, 2);
+/// mat.rb_mut().write(0, 1, 1.0);
+/// mat.rb_mut().write(1, 0, 2.0);
+/// assert_eq!(mat.rb().read(0, 1), 1.0);
+/// assert_eq!(mat.into_const().read(1, 0), 2.0);
+/// ```
+pub mod prelude {
+    pub use crate::{
+        AsGeneralizedMut, AsGeneralizedRef, AsOptSliceMut, AsSliceRbMut, AsStrRbMut, IntoConst,
+        Reborrow, ReborrowMut, ReborrowMutExt, SliceViewExt, TryReborrowMut,
+    };
+
+    #[cfg(feature = "alloc")]
+    pub use crate::{CowSliceRbMut, DequeRbMut};
+}
+
 /// Immutable reborrowing.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be reborrowed",
+    note = "the reborrow traits are implemented for references and views over them, and can be derived for user-defined views"
+)]
 pub trait Reborrow<'short, _Outlives = &'short Self> {
     type Target;
     #[must_use]
@@ -103,6 +377,210 @@ pub trait Reborrow<'short, _Outlives = &'short Self> {
 }
 
 /// Mutable reborrowing.
+///
+/// # Raw pointer views
+/// Views built on top of raw pointers, such as a unique pointer wrapping [`NonNull`], can be
+/// reborrowed by copying the pointer into a view with the shorter lifetime. This is sound because
+/// `rb_mut` takes `&'short mut self`: the original view is mutably borrowed, and so can't be used
+/// to access the pointee, for as long as the reborrowed view is alive.
+///
+/// [`NonNull`]: core::ptr::NonNull
+///
+/// ```
+/// use core::marker::PhantomData;
+/// use core::ptr::NonNull;
+/// use reborrow::ReborrowMut;
+///
+/// /// A unique pointer to a `T`, valid for `'a`.
+/// struct UniqueMut<'a, T> {
+///     ptr: NonNull<T>,
+///     // the view behaves like `&'a mut T` with respect to lifetimes and variance
+///     __marker: PhantomData<&'a mut T>,
+/// }
+///
+/// impl<'a, T> UniqueMut<'a, T> {
+///     fn new(value: &'a mut T) -> Self {
+///         Self {
+///             ptr: NonNull::from(value),
+///             __marker: PhantomData,
+///         }
+///     }
+///
+///     fn get_mut(&mut self) -> &mut T {
+///         // SAFETY: the pointer was created from a `&'a mut T`, and is only accessible
+///         // through this view for `'a`.
+///         unsafe { self.ptr.as_mut() }
+///     }
+/// }
+///
+/// impl<'short, 'a, T> ReborrowMut<'short> for UniqueMut<'a, T> {
+///     type Target = UniqueMut<'short, T>;
+///
+///     fn rb_mut(&'short mut self) -> Self::Target {
+///         // `self` is mutably borrowed for `'short`, so the new view has exclusive access to
+///         // the pointee for its whole lifetime.
+///         UniqueMut {
+///             ptr: self.ptr,
+///             __marker: PhantomData,
+///         }
+///     }
+/// }
+///
+/// fn incr(mut p: UniqueMut<'_, i32>) {
+///     *p.get_mut() += 1;
+/// }
+///
+/// let mut x = 0;
+/// let mut p = UniqueMut::new(&mut x);
+/// incr(p.rb_mut());
+/// incr(p.rb_mut());
+/// assert_eq!(*p.get_mut(), 2);
+/// ```
+///
+/// # Inherent methods
+/// Method resolution picks inherent methods over trait methods taking the same receiver, so if a
+/// view has an inherent method named `rb_mut`, `view.rb_mut()` calls it instead of the trait
+/// method. The trait method can still be called with `ReborrowMut::rb_mut(&mut view)`, and the
+/// derive's `rename` option can provide another name for it.
+///
+/// ```
+/// use reborrow::{ReborrowCopyTraits, ReborrowMut, ReborrowTraits};
+///
+/// #[derive(ReborrowCopyTraits)]
+/// struct CounterRef<'a> {
+///     count: &'a u32,
+/// }
+///
+/// #[derive(ReborrowTraits)]
+/// #[Const(CounterRef)]
+/// #[reborrow(rename = "reborrow_mut")]
+/// struct CounterMut<'a> {
+///     #[reborrow]
+///     count: &'a mut u32,
+/// }
+///
+/// impl CounterMut<'_> {
+///     /// Records a mutable reborrow, and returns the number of recorded reborrows.
+///     fn rb_mut(&mut self) -> u32 {
+///         *self.count += 1;
+///         *self.count
+///     }
+/// }
+///
+/// let mut count = 0;
+/// let mut view = CounterMut { count: &mut count };
+/// assert_eq!(view.rb_mut(), 1); // inherent method
+/// let r: CounterMut<'_> = ReborrowMut::rb_mut(&mut view); // trait method
+/// *r.count += 1;
+/// let r: CounterMut<'_> = view.reborrow_mut(); // trait method, renamed
+/// *r.count += 1;
+/// assert_eq!(view.rb_mut(), 4);
+/// ```
+///
+/// # Higher-ranked callbacks
+/// The `_Outlives` parameter requires `Self: 'short`, which views holding higher-ranked callbacks
+/// such as `Box<dyn for<'x> Fn(&'x mut i32)>` satisfy, since the higher-ranked lifetime is bound
+/// by the trait object, and not by the view. A callback owned by the view can be lent to the
+/// reborrowed view as a shared reference to the trait object.
+///
+/// ```
+/// use reborrow::ReborrowMut;
+///
+/// type Callback<'a> = dyn for<'x> Fn(&'x mut i32) + 'a;
+///
+/// struct Visitor<'a> {
+///     data: &'a mut [i32],
+///     f: Box<Callback<'a>>,
+/// }
+///
+/// struct VisitorMut<'a> {
+///     data: &'a mut [i32],
+///     f: &'a Callback<'a>,
+/// }
+///
+/// impl<'short, 'a> ReborrowMut<'short> for Visitor<'a> {
+///     type Target = VisitorMut<'short>;
+///
+///     fn rb_mut(&'short mut self) -> Self::Target {
+///         VisitorMut {
+///             data: self.data,
+///             f: &*self.f,
+///         }
+///     }
+/// }
+///
+/// fn visit(v: VisitorMut<'_>) {
+///     v.data.iter_mut().for_each(v.f);
+/// }
+///
+/// let offset = 2;
+/// let mut data = [1, 2];
+/// let mut visitor = Visitor {
+///     data: &mut data,
+///     f: Box::new(|x| *x += offset),
+/// };
+/// visit(visitor.rb_mut());
+/// visit(visitor.rb_mut());
+/// assert_eq!(visitor.data, [5, 6]);
+/// ```
+///
+/// # Invariant lifetimes
+/// The derive replaces all the lifetimes of a view with the reborrow lifetime, which doesn't work
+/// when one of them is behind a mutable reference, like the one of `&'a mut Formatter<'b>`, since
+/// `'b` can't be shortened. Such views should be reborrowed manually, keeping the invariant
+/// lifetime as is.
+///
+/// ```
+/// use core::fmt;
+/// use reborrow::ReborrowMut;
+///
+/// struct Indented<'a, 'b> {
+///     f: &'a mut fmt::Formatter<'b>,
+///     depth: usize,
+/// }
+///
+/// impl<'short, 'a, 'b> ReborrowMut<'short> for Indented<'a, 'b> {
+///     type Target = Indented<'short, 'b>;
+///
+///     fn rb_mut(&'short mut self) -> Self::Target {
+///         Indented {
+///             f: self.f,
+///             depth: self.depth,
+///         }
+///     }
+/// }
+///
+/// impl Indented<'_, '_> {
+///     fn line(self, s: &str) -> fmt::Result {
+///         writeln!(self.f, "{:indent$}{}", "", s, indent = 2 * self.depth)
+///     }
+/// }
+///
+/// struct Tree(&'static str, Vec<Tree>);
+///
+/// fn fmt_tree(tree: &Tree, mut out: Indented<'_, '_>) -> fmt::Result {
+///     out.rb_mut().line(tree.0)?;
+///     for child in &tree.1 {
+///         let mut child_out = out.rb_mut();
+///         child_out.depth += 1;
+///         fmt_tree(child, child_out)?;
+///     }
+///     Ok(())
+/// }
+///
+/// impl fmt::Display for Tree {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         fmt_tree(self, Indented { f, depth: 0 })
+///     }
+/// }
+///
+/// let tree = Tree("a", vec![Tree("b", vec![Tree("c", vec![])]), Tree("d", vec![])]);
+/// assert_eq!(tree.to_string(), "a\n  b\n    c\n  d\n");
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be mutably reborrowed",
+    note = "the reborrow traits are implemented for references and views over them, and can be derived for user-defined views"
+)]
 pub trait ReborrowMut<'short, _Outlives = &'short Self> {
     type Target;
     #[must_use]
@@ -110,12 +588,138 @@ pub trait ReborrowMut<'short, _Outlives = &'short Self> {
 }
 
 /// Consume a mutable reference to produce an immutable one.
+///
+/// View types that are already `Copy` are their own immutable variant, and should implement this
+/// trait with `Target = Self`. This is what `#[derive(ReborrowCopyTraits)]` does. There is no
+/// blanket impl over `Copy` types, since it would overlap with the impls for references and
+/// `Option`.
+///
+/// ```
+/// use reborrow::{IntoConst, Reborrow, ReborrowMut};
+///
+/// #[derive(Copy, Clone)]
+/// struct SliceRef<'a> {
+///     data: &'a [f64],
+/// }
+///
+/// impl<'short, 'a> Reborrow<'short> for SliceRef<'a> {
+///     type Target = SliceRef<'short>;
+///     fn rb(&'short self) -> Self::Target {
+///         *self
+///     }
+/// }
+///
+/// impl<'short, 'a> ReborrowMut<'short> for SliceRef<'a> {
+///     type Target = SliceRef<'short>;
+///     fn rb_mut(&'short mut self) -> Self::Target {
+///         *self
+///     }
+/// }
+///
+/// impl<'a> IntoConst for SliceRef<'a> {
+///     type Target = SliceRef<'a>;
+///     fn into_const(self) -> Self::Target {
+///         self
+///     }
+/// }
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` can't be converted to its const variant",
+    note = "the reborrow traits are implemented for references and views over them, and can be derived for user-defined views"
+)]
 pub trait IntoConst {
     type Target;
     #[must_use]
     fn into_const(self) -> Self::Target;
 }
 
+/// Extension methods for [`ReborrowMut`] types.
+///
+/// There is intentionally no method returning both the reborrowed view and `&mut self`: the view
+/// mutably borrows `self`, so the two could never be used at the same time. Builder-style chains
+/// should instead consume the reborrowed view, after which the original becomes usable again.
+pub trait ReborrowMutExt<'short, _Outlives = &'short Self>: ReborrowMut<'short, _Outlives> {
+    /// Same as [`ReborrowMut::rb_mut`]. `self` is usable again once the returned view is dropped.
+    #[inline]
+    #[must_use]
+    fn rb_mut_keep(&'short mut self) -> Self::Target {
+        self.rb_mut()
+    }
+
+    /// Mutably reborrows `self`, and passes the reborrowed view to `f`. `self` is usable again
+    /// once `f` returns.
+    #[inline]
+    fn modify_rb_mut<R>(&'short mut self, f: impl FnOnce(Self::Target) -> R) -> R {
+        f(self.rb_mut())
+    }
+}
+
+impl<'short, T: ?Sized + ReborrowMut<'short>> ReborrowMutExt<'short> for T {}
+
+/// Object safe variant of [`ReborrowMut`], for views that reborrow to `&mut T`.
+///
+/// [`ReborrowMut`] is generic over the reborrow lifetime and has a target type depending on it,
+/// so it can't be used as a trait object. This trait fixes the target to a mutable reference to
+/// `T`, which allows storing heterogeneous views behind `dyn DynReborrowMut<T>`. Views with other
+/// targets can't be erased this way, since their target can't be named without its lifetime, and
+/// isn't `'static` (which rules out returning a `Box<dyn Any>`).
+///
+/// This trait is implemented for all types that implement [`ReborrowMut`] with a `&mut T` target.
+pub trait DynReborrowMut<T: ?Sized> {
+    #[must_use]
+    fn dyn_rb_mut(&mut self) -> &mut T;
+}
+
+impl<V, T> DynReborrowMut<T> for V
+where
+    V: ?Sized + for<'short> ReborrowMut<'short, Target = &'short mut T>,
+    T: ?Sized,
+{
+    #[inline]
+    fn dyn_rb_mut(&mut self) -> &mut T {
+        self.rb_mut()
+    }
+}
+
+/// Fallible mutable reborrowing, for views that can only be reborrowed when they're not currently
+/// aliased, such as views backed by a borrow flag.
+///
+/// This trait is implemented for all types that implement [`ReborrowMut`], with
+/// [`Infallible`](core::convert::Infallible) as the error. For the same reason, it can't also be
+/// implemented for `Option<T>` where `T` only implements `TryReborrowMut`, and
+/// [`try_rb_mut_option`] should be used instead.
+pub trait TryReborrowMut<'short, _Outlives = &'short Self> {
+    type Target;
+    type Error;
+    #[allow(clippy::double_must_use)]
+    #[must_use]
+    fn try_rb_mut(&'short mut self) -> Result<Self::Target, Self::Error>;
+}
+
+impl<'short, T> TryReborrowMut<'short> for T
+where
+    T: ?Sized + ReborrowMut<'short>,
+{
+    type Target = T::Target;
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn try_rb_mut(&'short mut self) -> Result<Self::Target, Self::Error> {
+        Ok(self.rb_mut())
+    }
+}
+
+/// Fallibly reborrows the view contained in an `Option`, if any.
+#[inline]
+pub fn try_rb_mut_option<'short, T>(
+    view: &'short mut Option<T>,
+) -> Result<Option<T::Target>, T::Error>
+where
+    T: TryReborrowMut<'short>,
+{
+    view.as_mut().map(T::try_rb_mut).transpose()
+}
+
 /// This trait is similar to [`std::convert::AsRef`], but works with generalized reference
 /// types, instead of being limited to native Rust references.
 pub trait AsGeneralizedRef<'short, Target, _Outlives = &'short Self> {
@@ -130,6 +734,132 @@ pub trait AsGeneralizedMut<'short, Target, _Outlives = &'short Self> {
     fn as_generalized_mut(&'short mut self) -> Target;
 }
 
+/// Implements [`Reborrow`] and [`ReborrowMut`] for smart pointer types, with references to their
+/// [`Deref::Target`](core::ops::Deref::Target) as the targets.
+///
+/// A blanket impl over all `DerefMut` types isn't possible, even behind a feature, since it would
+/// overlap with the impls for references, `Option`, arrays, etc. This macro opts in concrete
+/// types instead.
+///
+/// ```
+/// use core::ops::{Deref, DerefMut};
+/// use reborrow::{Reborrow, ReborrowMut};
+///
+/// struct Tracked {
+///     value: i32,
+///     writes: u32,
+/// }
+///
+/// impl Deref for Tracked {
+///     type Target = i32;
+///     fn deref(&self) -> &i32 {
+///         &self.value
+///     }
+/// }
+///
+/// impl DerefMut for Tracked {
+///     fn deref_mut(&mut self) -> &mut i32 {
+///         self.writes += 1;
+///         &mut self.value
+///     }
+/// }
+///
+/// reborrow::reborrow_deref!(Tracked);
+///
+/// fn incr(x: &mut i32) {
+///     *x += 1;
+/// }
+///
+/// let mut t = Tracked { value: 0, writes: 0 };
+/// incr(t.rb_mut());
+/// incr(t.rb_mut());
+/// assert_eq!((*t.rb(), t.writes), (2, 2));
+/// ```
+#[macro_export]
+macro_rules! reborrow_deref {
+    ($($ty: ty),* $(,)?) => {$(
+        impl<'short> $crate::Reborrow<'short> for $ty {
+            type Target = &'short <$ty as ::core::ops::Deref>::Target;
+
+            #[inline]
+            fn rb(&'short self) -> Self::Target {
+                self
+            }
+        }
+
+        impl<'short> $crate::ReborrowMut<'short> for $ty {
+            type Target = &'short mut <$ty as ::core::ops::Deref>::Target;
+
+            #[inline]
+            fn rb_mut(&'short mut self) -> Self::Target {
+                self
+            }
+        }
+    )*};
+}
+
+/// Implements [`Reborrow`], [`ReborrowMut`] and [`IntoConst`] for `Copy` value types, with `Self`
+/// as the target.
+///
+/// # Guidance
+/// Plain values stored in a view, such as sizes, flags or timestamps, are copied into the
+/// reborrowed view rather than reborrowed. They don't need any trait impl to be used as regular
+/// fields, but implementing the reborrow traits lets them be passed where a reborrowable type is
+/// expected, e.g. as the type parameter of a generic view, or as a `#[reborrow]` field.
+///
+/// This crate only does this for [`RangeFull`](core::ops::RangeFull), and not for the primitive
+/// scalars or other value types such as [`Duration`](core::time::Duration) and the `std::net`
+/// address types. Method call syntax
+/// picks the impl for the pointee when one exists, so `view.rb_mut()` with `view: &mut Duration`
+/// would copy the duration, instead of reborrowing the reference. Such types can be wrapped in a
+/// newtype implementing the reborrow traits with this macro instead.
+///
+/// Note that since the target is a copy, mutating it through `rb_mut` doesn't affect the original.
+///
+/// ```
+/// use reborrow::ReborrowMut;
+///
+/// #[derive(Copy, Clone, Debug, PartialEq)]
+/// struct Celsius(f64);
+///
+/// reborrow::reborrow_copy_passthrough!(Celsius);
+///
+/// let mut t = Celsius(21.5);
+/// let copy = t.rb_mut();
+/// assert_eq!(copy, t);
+/// ```
+#[macro_export]
+macro_rules! reborrow_copy_passthrough {
+    ($($ty: ty),* $(,)?) => {$(
+        impl<'short> $crate::Reborrow<'short> for $ty {
+            type Target = $ty;
+
+            #[inline]
+            fn rb(&'short self) -> Self::Target {
+                *self
+            }
+        }
+
+        impl<'short> $crate::ReborrowMut<'short> for $ty {
+            type Target = $ty;
+
+            #[inline]
+            fn rb_mut(&'short mut self) -> Self::Target {
+                *self
+            }
+        }
+
+        impl $crate::IntoConst for $ty {
+            type Target = $ty;
+
+            #[inline]
+            fn into_const(self) -> Self::Target {
+                self
+            }
+        }
+    )*};
+}
+
 impl<'short, T: ?Sized + AsRef<Target>, Target: ?Sized> AsGeneralizedRef<'short, &'short Target>
     for T
 {
@@ -148,7 +878,7 @@ impl<'short, T: ?Sized + AsMut<Target>, Target: ?Sized> AsGeneralizedMut<'short,
     }
 }
 
-impl<'short, 'a, T> Reborrow<'short> for &'a T
+impl<'short, T> Reborrow<'short> for &T
 where
     T: ?Sized,
 {
@@ -160,7 +890,7 @@ where
     }
 }
 
-impl<'short, 'a, T> ReborrowMut<'short> for &'a T
+impl<'short, T> ReborrowMut<'short> for &T
 where
     T: ?Sized,
 {
@@ -184,7 +914,7 @@ where
     }
 }
 
-impl<'short, 'a, T> Reborrow<'short> for &'a mut T
+impl<'short, T> Reborrow<'short> for &mut T
 where
     T: ?Sized,
 {
@@ -196,7 +926,7 @@ where
     }
 }
 
-impl<'short, 'a, T> ReborrowMut<'short> for &'a mut T
+impl<'short, T> ReborrowMut<'short> for &mut T
 where
     T: ?Sized,
 {
@@ -228,10 +958,7 @@ where
 
     #[inline]
     fn rb(&'short self) -> Self::Target {
-        match self {
-            &None => None,
-            &Some(ref x) => Some(x.rb()),
-        }
+        self.as_ref().map(T::rb)
     }
 }
 
@@ -243,10 +970,7 @@ where
 
     #[inline]
     fn rb_mut(&'short mut self) -> Self::Target {
-        match self {
-            &mut None => None,
-            &mut Some(ref mut x) => Some(x.rb_mut()),
-        }
+        self.as_mut().map(T::rb_mut)
     }
 }
 
@@ -258,10 +982,7 @@ where
 
     #[inline]
     fn into_const(self) -> Self::Target {
-        match self {
-            None => None,
-            Some(x) => Some(x.into_const()),
-        }
+        self.map(T::into_const)
     }
 }
 
@@ -270,10 +991,7 @@ impl<'short, T: AsGeneralizedRef<'short, Target>, Target> AsGeneralizedRef<'shor
 {
     #[inline]
     fn as_generalized_ref(&'short self) -> Option<Target> {
-        match self {
-            None => None,
-            &Some(ref x) => Some(x.as_generalized_ref()),
-        }
+        self.as_ref().map(T::as_generalized_ref)
     }
 }
 
@@ -282,51 +1000,2211 @@ impl<'short, T: AsGeneralizedMut<'short, Target>, Target> AsGeneralizedMut<'shor
 {
     #[inline]
     fn as_generalized_mut(&'short mut self) -> Option<Target> {
-        match self {
-            None => None,
-            &mut Some(ref mut x) => Some(x.as_generalized_mut()),
-        }
+        self.as_mut().map(T::as_generalized_mut)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<'short, T, E> Reborrow<'short> for Result<T, E>
+where
+    T: Reborrow<'short>,
+    E: Reborrow<'short>,
+{
+    type Target = Result<T::Target, E::Target>;
 
-    #[test]
-    fn option() {
-        let mut a = 0;
-        let mut opt = Some(&mut a);
-        let opt_mut = &mut opt;
-        let _ = opt_mut.rb_mut();
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self.as_ref().map(T::rb).map_err(E::rb)
     }
+}
 
-    #[test]
-    fn custom_view_type() {
-        struct MyViewType<'a> {
-            r: &'a mut i32,
+impl<'short, T, E> ReborrowMut<'short> for Result<T, E>
+where
+    T: ReborrowMut<'short>,
+    E: ReborrowMut<'short>,
+{
+    type Target = Result<T::Target, E::Target>;
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        self.as_mut().map(T::rb_mut).map_err(E::rb_mut)
+    }
+}
+
+impl<T, E> IntoConst for Result<T, E>
+where
+    T: IntoConst,
+    E: IntoConst,
+{
+    type Target = Result<T::Target, E::Target>;
+
+    #[inline]
+    fn into_const(self) -> Self::Target {
+        self.map(T::into_const).map_err(E::into_const)
+    }
+}
+
+impl<'short, T: AsGeneralizedRef<'short, Target>, E: 'short, Target>
+    AsGeneralizedRef<'short, Result<Target, &'short E>> for Result<T, E>
+{
+    #[inline]
+    fn as_generalized_ref(&'short self) -> Result<Target, &'short E> {
+        self.as_ref().map(T::as_generalized_ref)
+    }
+}
+
+impl<'short, T: AsGeneralizedMut<'short, Target>, E: 'short, Target>
+    AsGeneralizedMut<'short, Result<Target, &'short mut E>> for Result<T, E>
+{
+    #[inline]
+    fn as_generalized_mut(&'short mut self) -> Result<Target, &'short mut E> {
+        self.as_mut().map(T::as_generalized_mut)
+    }
+}
+
+// allows reborrowing `Result<T, Infallible>`, whose error arm can never occur
+impl<'short> Reborrow<'short> for core::convert::Infallible {
+    type Target = core::convert::Infallible;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        match *self {}
+    }
+}
+
+impl<'short> ReborrowMut<'short> for core::convert::Infallible {
+    type Target = core::convert::Infallible;
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        match *self {}
+    }
+}
+
+impl IntoConst for core::convert::Infallible {
+    type Target = core::convert::Infallible;
+
+    #[inline]
+    fn into_const(self) -> Self::Target {
+        match self {}
+    }
+}
+
+impl<'short, T, const N: usize> Reborrow<'short> for [T; N]
+where
+    T: Reborrow<'short>,
+{
+    type Target = [T::Target; N];
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self.each_ref().map(T::rb)
+    }
+}
+
+impl<'short, T, const N: usize> ReborrowMut<'short> for [T; N]
+where
+    T: ReborrowMut<'short>,
+{
+    type Target = [T::Target; N];
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        self.each_mut().map(T::rb_mut)
+    }
+}
+
+impl<T, const N: usize> IntoConst for [T; N]
+where
+    T: IntoConst,
+{
+    type Target = [T::Target; N];
+
+    #[inline]
+    fn into_const(self) -> Self::Target {
+        self.map(T::into_const)
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($t: ident $idx: tt),*) => {
+        impl<'short, $($t),*> Reborrow<'short> for ($($t,)*)
+        where
+            $($t: Reborrow<'short>,)*
+        {
+            type Target = ($($t::Target,)*);
+
+            #[inline]
+            fn rb(&'short self) -> Self::Target {
+                ($(self.$idx.rb(),)*)
+            }
         }
 
-        impl<'short, 'a> ReborrowMut<'short> for MyViewType<'a> {
-            type Target = MyViewType<'short>;
+        impl<'short, $($t),*> ReborrowMut<'short> for ($($t,)*)
+        where
+            $($t: ReborrowMut<'short>,)*
+        {
+            type Target = ($($t::Target,)*);
 
+            #[inline]
             fn rb_mut(&'short mut self) -> Self::Target {
-                MyViewType { r: self.r }
+                ($(self.$idx.rb_mut(),)*)
             }
         }
 
-        fn takes_mut_option(_o: Option<MyViewType>) {}
+        impl<$($t),*> IntoConst for ($($t,)*)
+        where
+            $($t: IntoConst,)*
+        {
+            type Target = ($($t::Target,)*);
 
-        let mut x = 0;
-        let mut o = Some(MyViewType { r: &mut x });
-        takes_mut_option(o.rb_mut());
-        takes_mut_option(o.rb_mut());
-        drop(o);
+            #[inline]
+            fn into_const(self) -> Self::Target {
+                ($(self.$idx.into_const(),)*)
+            }
+        }
+    };
+}
+
+impl_tuple!(T0 0);
+impl_tuple!(T0 0, T1 1);
+impl_tuple!(T0 0, T1 1, T2 2);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+impl_tuple!(T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+
+#[cfg(feature = "either")]
+impl<'short, L, R> Reborrow<'short> for either::Either<L, R>
+where
+    L: Reborrow<'short>,
+    R: Reborrow<'short>,
+{
+    type Target = either::Either<L::Target, R::Target>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        match self {
+            either::Either::Left(l) => either::Either::Left(l.rb()),
+            either::Either::Right(r) => either::Either::Right(r.rb()),
+        }
     }
+}
 
-    #[test]
-    fn as_ref() {
-        let v = [()];
-        let _r: &[()] = v.as_generalized_ref();
+#[cfg(feature = "either")]
+impl<'short, L, R> ReborrowMut<'short> for either::Either<L, R>
+where
+    L: ReborrowMut<'short>,
+    R: ReborrowMut<'short>,
+{
+    type Target = either::Either<L::Target, R::Target>;
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        match self {
+            either::Either::Left(l) => either::Either::Left(l.rb_mut()),
+            either::Either::Right(r) => either::Either::Right(r.rb_mut()),
+        }
+    }
+}
+
+#[cfg(feature = "either")]
+impl<L, R> IntoConst for either::Either<L, R>
+where
+    L: IntoConst,
+    R: IntoConst,
+{
+    type Target = either::Either<L::Target, R::Target>;
+
+    #[inline]
+    fn into_const(self) -> Self::Target {
+        match self {
+            either::Either::Left(l) => either::Either::Left(l.into_const()),
+            either::Either::Right(r) => either::Either::Right(r.into_const()),
+        }
+    }
+}
+
+// `&Cell<T>` (including `&Cell<[T]>`) is already covered by the impl for `&T`. This allows cells
+// that are owned by a view to be shared with the reborrowed view.
+impl<'short, T> Reborrow<'short> for core::cell::Cell<T>
+where
+    T: ?Sized,
+{
+    type Target = &'short core::cell::Cell<T>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self
+    }
+}
+
+/// Reborrowing preserves the remaining items of the iterator.
+impl<'short, T> Reborrow<'short> for core::slice::Iter<'_, T> {
+    type Target = core::slice::Iter<'short, T>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self.as_slice().iter()
+    }
+}
+
+/// Reborrowing preserves the remaining items of the iterator, which can only be accessed
+/// immutably.
+///
+/// There is no [`ReborrowMut`] impl for `IterMut<'_, T>`: its remaining items can only be accessed
+/// mutably by consuming the iterator, with [`IterMut::into_slice`](core::slice::IterMut::into_slice).
+impl<'short, T> Reborrow<'short> for core::slice::IterMut<'_, T> {
+    type Target = core::slice::Iter<'short, T>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self.as_slice().iter()
+    }
+}
+
+// atomics are shared between views, like cells, so that they can be used as fields.
+macro_rules! impl_atomic {
+    ($size: literal: $($ty: ident),*) => {$(
+        #[cfg(target_has_atomic = $size)]
+        impl<'short> Reborrow<'short> for core::sync::atomic::$ty {
+            type Target = &'short core::sync::atomic::$ty;
+
+            #[inline]
+            fn rb(&'short self) -> Self::Target {
+                self
+            }
+        }
+    )*};
+}
+
+impl_atomic!("8": AtomicBool, AtomicU8, AtomicI8);
+impl_atomic!("16": AtomicU16, AtomicI16);
+impl_atomic!("32": AtomicU32, AtomicI32);
+impl_atomic!("64": AtomicU64, AtomicI64);
+impl_atomic!("ptr": AtomicUsize, AtomicIsize);
+
+#[cfg(target_has_atomic = "ptr")]
+impl<'short, T> Reborrow<'short> for core::sync::atomic::AtomicPtr<T> {
+    type Target = &'short core::sync::atomic::AtomicPtr<T>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self
+    }
+}
+
+impl<'short, T> Reborrow<'short> for core::mem::ManuallyDrop<T>
+where
+    T: Reborrow<'short>,
+{
+    type Target = core::mem::ManuallyDrop<T::Target>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        core::mem::ManuallyDrop::new((**self).rb())
+    }
+}
+
+impl<'short, T> ReborrowMut<'short> for core::mem::ManuallyDrop<T>
+where
+    T: ReborrowMut<'short>,
+{
+    type Target = core::mem::ManuallyDrop<T::Target>;
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        core::mem::ManuallyDrop::new((**self).rb_mut())
+    }
+}
+
+impl<T> IntoConst for core::mem::ManuallyDrop<T>
+where
+    T: IntoConst,
+{
+    type Target = core::mem::ManuallyDrop<T::Target>;
+
+    #[inline]
+    fn into_const(self) -> Self::Target {
+        core::mem::ManuallyDrop::new(core::mem::ManuallyDrop::into_inner(self).into_const())
+    }
+}
+
+impl<'short, T> Reborrow<'short> for core::cell::Ref<'_, T>
+where
+    T: ?Sized,
+{
+    type Target = &'short T;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self
+    }
+}
+
+// this also covers guards projected with `RefMut::map`. the `RefCell` stays borrowed for as long as
+// the guard is alive, which outlives the reborrowed reference.
+impl<'short, T> Reborrow<'short> for core::cell::RefMut<'_, T>
+where
+    T: ?Sized,
+{
+    type Target = &'short T;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self
+    }
+}
+
+impl<'short, T> ReborrowMut<'short> for core::cell::RefMut<'_, T>
+where
+    T: ?Sized,
+{
+    type Target = &'short mut T;
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        self
+    }
+}
+
+/// Reborrowing forces the evaluation of the lazy value.
+#[cfg(feature = "lazy")]
+// the `lazy` feature requires Rust 1.80, above the MSRV of the rest of the crate.
+#[allow(clippy::incompatible_msrv)]
+impl<'short, T, F> Reborrow<'short> for core::cell::LazyCell<T, F>
+where
+    F: FnOnce() -> T,
+{
+    type Target = &'short T;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        core::cell::LazyCell::force(self)
+    }
+}
+
+/// Reborrowing forces the evaluation of the lazy value.
+#[cfg(all(feature = "lazy", feature = "std"))]
+// the `lazy` feature requires Rust 1.80, above the MSRV of the rest of the crate.
+#[allow(clippy::incompatible_msrv)]
+impl<'short, T, F> Reborrow<'short> for std::sync::LazyLock<T, F>
+where
+    F: FnOnce() -> T,
+{
+    type Target = &'short T;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        std::sync::LazyLock::force(self)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'short, T, const N: usize> Reborrow<'short> for smallvec::SmallVec<[T; N]>
+where
+    T: Reborrow<'short>,
+{
+    type Target = smallvec::SmallVec<[T::Target; N]>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self.iter().map(T::rb).collect()
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<'short, T, const N: usize> ReborrowMut<'short> for smallvec::SmallVec<[T; N]>
+where
+    T: ReborrowMut<'short>,
+{
+    type Target = smallvec::SmallVec<[T::Target; N]>;
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        self.iter_mut().map(T::rb_mut).collect()
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<T, const N: usize> IntoConst for smallvec::SmallVec<[T; N]>
+where
+    T: IntoConst,
+{
+    type Target = smallvec::SmallVec<[T::Target; N]>;
+
+    #[inline]
+    fn into_const(self) -> Self::Target {
+        self.into_iter().map(T::into_const).collect()
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'short, T, const N: usize> Reborrow<'short> for arrayvec::ArrayVec<T, N>
+where
+    T: Reborrow<'short>,
+{
+    type Target = arrayvec::ArrayVec<T::Target, N>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self.iter().map(T::rb).collect()
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<'short, T, const N: usize> ReborrowMut<'short> for arrayvec::ArrayVec<T, N>
+where
+    T: ReborrowMut<'short>,
+{
+    type Target = arrayvec::ArrayVec<T::Target, N>;
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        self.iter_mut().map(T::rb_mut).collect()
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<T, const N: usize> IntoConst for arrayvec::ArrayVec<T, N>
+where
+    T: IntoConst,
+{
+    type Target = arrayvec::ArrayVec<T::Target, N>;
+
+    #[inline]
+    fn into_const(self) -> Self::Target {
+        self.into_iter().map(T::into_const).collect()
+    }
+}
+
+#[cfg(feature = "generic-array")]
+impl<'short, T, N> Reborrow<'short> for generic_array::GenericArray<T, N>
+where
+    T: Reborrow<'short>,
+    N: generic_array::ArrayLength,
+{
+    type Target = generic_array::GenericArray<T::Target, N>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self.iter().map(T::rb).collect()
+    }
+}
+
+#[cfg(feature = "generic-array")]
+impl<'short, T, N> ReborrowMut<'short> for generic_array::GenericArray<T, N>
+where
+    T: ReborrowMut<'short>,
+    N: generic_array::ArrayLength,
+{
+    type Target = generic_array::GenericArray<T::Target, N>;
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        self.iter_mut().map(T::rb_mut).collect()
+    }
+}
+
+#[cfg(feature = "generic-array")]
+impl<T, N> IntoConst for generic_array::GenericArray<T, N>
+where
+    T: IntoConst,
+    N: generic_array::ArrayLength,
+{
+    type Target = generic_array::GenericArray<T::Target, N>;
+
+    #[inline]
+    fn into_const(self) -> Self::Target {
+        self.into_iter().map(T::into_const).collect()
+    }
+}
+
+reborrow_copy_passthrough!(core::ops::RangeFull);
+
+// Pinning is preserved by going through `Pin::as_ref` and `Pin::as_mut`, which only ever hand out
+// pinned references to the pointee, so it can't be moved out through the reborrowed view.
+impl<'short, P> Reborrow<'short> for core::pin::Pin<P>
+where
+    P: core::ops::Deref,
+{
+    type Target = core::pin::Pin<&'short P::Target>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self.as_ref()
+    }
+}
+
+impl<'short, P> ReborrowMut<'short> for core::pin::Pin<P>
+where
+    P: core::ops::DerefMut,
+{
+    type Target = core::pin::Pin<&'short mut P::Target>;
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        self.as_mut()
+    }
+}
+
+impl<'a, T> IntoConst for core::pin::Pin<&'a mut T>
+where
+    T: ?Sized,
+{
+    type Target = core::pin::Pin<&'a T>;
+
+    #[inline]
+    fn into_const(self) -> Self::Target {
+        self.into_ref()
+    }
+}
+
+// There is no `IntoConst` impl for `Box<T>`: the box owns its contents, so producing a shared
+// reference out of it would require either consuming the box or leaking it.
+// For the same reason, a boxed view reborrows to a reference to the view, and not to a boxed view
+// with a shorter lifetime, which rules out recursive views such as
+// `struct NodeMut<'a> { val: &'a mut i32, next: Option<Box<NodeMut<'a>>> }`. The view should
+// instead borrow the recursive structure, e.g. `Option<&'a mut Node>`.
+#[cfg(feature = "alloc")]
+impl<'short, T> Reborrow<'short> for alloc::boxed::Box<T>
+where
+    T: ?Sized,
+{
+    type Target = &'short T;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'short, T> ReborrowMut<'short> for alloc::boxed::Box<T>
+where
+    T: ?Sized,
+{
+    type Target = &'short mut T;
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        self
+    }
+}
+
+/// Reborrowing borrows the contents, whether they're borrowed or owned. For example,
+/// `Cow<'_, [u8]>` reborrows to `&[u8]`.
+#[cfg(feature = "alloc")]
+impl<'short, B> Reborrow<'short> for alloc::borrow::Cow<'_, B>
+where
+    B: ?Sized + alloc::borrow::ToOwned,
+{
+    type Target = &'short B;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'short> Reborrow<'short> for std::io::IoSlice<'_> {
+    type Target = &'short [u8];
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'short> Reborrow<'short> for std::io::IoSliceMut<'_> {
+    type Target = &'short [u8];
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'short> ReborrowMut<'short> for std::io::IoSliceMut<'_> {
+    type Target = &'short mut [u8];
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        self
+    }
+}
+
+// a `CString` gives no mutable access to its contents, so it's only reborrowed immutably.
+#[cfg(feature = "alloc")]
+impl<'short> Reborrow<'short> for alloc::ffi::CString {
+    type Target = &'short core::ffi::CStr;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self.as_c_str()
+    }
+}
+
+// sets give no mutable access to their elements, so they're only reborrowed as a whole.
+#[cfg(feature = "alloc")]
+impl<'short, T> Reborrow<'short> for alloc::collections::BTreeSet<T> {
+    type Target = &'short alloc::collections::BTreeSet<T>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'short, T, S> Reborrow<'short> for std::collections::HashSet<T, S> {
+    type Target = &'short std::collections::HashSet<T, S>;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'short> Reborrow<'short> for std::path::PathBuf {
+    type Target = &'short std::path::Path;
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        self.as_path()
+    }
+}
+
+/// Swaps the values pointed to by two views, leaving both views usable afterwards.
+#[inline]
+pub fn swap_rb_mut<'short, T, U>(a: &'short mut T, b: &'short mut T)
+where
+    T: ReborrowMut<'short, Target = &'short mut U>,
+    U: 'short,
+{
+    core::mem::swap(a.rb_mut(), b.rb_mut());
+}
+
+/// Replaces the value pointed to by a view with `new`, and returns the old value, leaving the view
+/// usable afterwards.
+#[inline]
+pub fn replace_rb_mut<'short, T, U>(view: &'short mut T, new: U) -> U
+where
+    T: ReborrowMut<'short, Target = &'short mut U>,
+    U: 'short,
+{
+    core::mem::replace(view.rb_mut(), new)
+}
+
+/// Mutably reborrows a view, and indexes into it. The view is usable again once the returned
+/// reference is dropped.
+#[inline]
+pub fn index_rb_mut<'short, T, U, I>(view: &'short mut T, index: I) -> &'short mut U::Output
+where
+    T: ReborrowMut<'short, Target = &'short mut U>,
+    U: ?Sized + core::ops::IndexMut<I> + 'short,
+{
+    &mut view.rb_mut()[index]
+}
+
+/// Mutably reborrows a view, and passes the reborrowed view to the fallible transformation `f`.
+/// The view is usable again once the result is dropped, whether `f` succeeded or not.
+#[inline]
+pub fn try_map_rb_mut<'short, T, U, E>(
+    view: &'short mut T,
+    f: impl FnOnce(T::Target) -> Result<U, E>,
+) -> Result<U, E>
+where
+    T: ReborrowMut<'short>,
+{
+    f(view.rb_mut())
+}
+
+/// Reborrows the contents of a `RefMut` guard, and projects them with `f`. The guard is usable
+/// again once the returned reference is dropped.
+#[inline]
+pub fn map_ref_mut<'short, T, U>(
+    guard: &'short mut core::cell::RefMut<'_, T>,
+    f: impl FnOnce(&'short mut T) -> &'short mut U,
+) -> &'short mut U
+where
+    T: ?Sized,
+    U: ?Sized,
+{
+    f(guard.rb_mut())
+}
+
+/// Mutably reborrows `a` if `cond` is true, and `b` otherwise. Both views are usable again once the
+/// returned view is dropped.
+#[cfg(feature = "either")]
+#[inline]
+pub fn either_rb_mut<'short, A, B>(
+    cond: bool,
+    a: &'short mut A,
+    b: &'short mut B,
+) -> either::Either<A::Target, B::Target>
+where
+    A: ReborrowMut<'short>,
+    B: ReborrowMut<'short>,
+{
+    if cond {
+        either::Either::Left(a.rb_mut())
+    } else {
+        either::Either::Right(b.rb_mut())
+    }
+}
+
+/// Returns an iterator over the reborrowed views of a slice (or `Vec`, `SmallVec`, ...). The views
+/// are usable again once the iterator and the reborrowed views are dropped.
+#[inline]
+pub fn rb_iter_mut<'short, T>(views: &'short mut [T]) -> impl Iterator<Item = T::Target>
+where
+    T: ReborrowMut<'short>,
+{
+    views.iter_mut().map(T::rb_mut)
+}
+
+/// Returns an iterator over mutable chunks of a slice view, each of length `size` except possibly
+/// the last one. The view is usable again once the iterator and the chunks are dropped.
+///
+/// # Panics
+/// Panics if `size` is zero.
+#[inline]
+pub fn rb_chunks_mut<'short, T>(
+    view: &'short mut &mut [T],
+    size: usize,
+) -> impl Iterator<Item = &'short mut [T]> {
+    view.chunks_mut(size)
+}
+
+/// Returns a parallel iterator over the reborrowed views of a slice. Each view is reborrowed
+/// exactly once, so the reborrowed views are disjoint as long as the views themselves are.
+#[cfg(feature = "rayon")]
+#[inline]
+pub fn rb_par_iter_mut<'short, T>(
+    views: &'short mut [T],
+) -> impl rayon::iter::IndexedParallelIterator<Item = T::Target>
+where
+    T: ReborrowMut<'short> + Send,
+    T::Target: Send,
+{
+    use rayon::iter::{IntoParallelRefMutIterator, ParallelIterator};
+    views.par_iter_mut().map(T::rb_mut)
+}
+
+/// Reborrows each view of a slice into a `Vec`. Each view is reborrowed exactly once, so the
+/// reborrowed views are independent as long as the views themselves are. The views are usable
+/// again once the `Vec` is dropped.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn collect_rb_mut<'short, T>(views: &'short mut [T]) -> alloc::vec::Vec<T::Target>
+where
+    T: ReborrowMut<'short>,
+{
+    views.iter_mut().map(T::rb_mut).collect()
+}
+
+/// Sorts a slice of views in place, by comparing their immutably reborrowed views. The sort is
+/// stable.
+///
+/// The comparison can't use mutably reborrowed views, since sorting only gives shared access to
+/// the compared elements.
+// the stable `sort_by` allocates, so it's only available with `alloc`.
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn sort_rb_by<T, F>(views: &mut [T], mut cmp: F)
+where
+    T: for<'short> Reborrow<'short>,
+    F: for<'short> FnMut(
+        <T as Reborrow<'short>>::Target,
+        <T as Reborrow<'short>>::Target,
+    ) -> core::cmp::Ordering,
+{
+    views.sort_by(|a, b| cmp(a.rb(), b.rb()))
+}
+
+/// Reborrows each view of a slice (or `Vec`), and folds over the reborrowed views. The views are
+/// usable again afterwards.
+#[inline]
+pub fn fold_rb_mut<'short, T, A>(
+    views: &'short mut [T],
+    init: A,
+    f: impl FnMut(A, T::Target) -> A,
+) -> A
+where
+    T: ReborrowMut<'short>,
+{
+    views.iter_mut().map(T::rb_mut).fold(init, f)
+}
+
+/// Reborrows each view of an array, and passes the reborrowed view to `f`. The views are usable
+/// again afterwards.
+#[inline]
+pub fn for_each_rb_mut<'short, T, const N: usize>(
+    views: &'short mut [T; N],
+    f: impl FnMut(T::Target),
+) where
+    T: ReborrowMut<'short>,
+{
+    views.iter_mut().map(T::rb_mut).for_each(f)
+}
+
+/// Returns an iterator over the keys and reborrowed values of a map of views. The values are
+/// usable again once the iterator and the reborrowed views are dropped.
+///
+/// Like [`HashMap::iter_mut`](std::collections::HashMap::iter_mut), the iteration order is
+/// unspecified, and may differ between maps with the same entries.
+#[cfg(feature = "std")]
+#[inline]
+pub fn rb_values_mut<'short, K, V, S>(
+    map: &'short mut std::collections::HashMap<K, V, S>,
+) -> impl Iterator<Item = (&'short K, V::Target)>
+where
+    V: ReborrowMut<'short>,
+{
+    map.iter_mut().map(|(k, v)| (k, v.rb_mut()))
+}
+
+/// Mutably reborrows a view over a slice of uninitialized values as a slice of initialized ones.
+///
+/// # Safety
+/// All the elements of the slice must be initialized.
+#[cfg(feature = "raw")]
+#[inline]
+#[must_use]
+pub unsafe fn assume_init_mut_rb<'short, V, T>(view: &'short mut V) -> &'short mut [T]
+where
+    V: ReborrowMut<'short, Target = &'short mut [core::mem::MaybeUninit<T>]>,
+{
+    let slice = view.rb_mut();
+    // SAFETY: `MaybeUninit<T>` has the same layout as `T`, and the caller guarantees that all
+    // the elements are initialized.
+    unsafe { &mut *(slice as *mut [core::mem::MaybeUninit<T>] as *mut [T]) }
+}
+
+/// Mutable slice view stored as a pointer and a length, as an example of a fat pointer view.
+///
+/// The view behaves like `&'a mut [T]`, and reborrowing it copies the pointer and the length into
+/// a view with a shorter lifetime. This is sound since [`ReborrowMut::rb_mut`] mutably borrows the
+/// original view for as long as the reborrowed one is alive, so the two can't be used to access
+/// the elements at the same time.
+#[cfg(feature = "raw")]
+pub struct RawSliceMut<'a, T> {
+    ptr: core::ptr::NonNull<T>,
+    len: usize,
+    __marker: core::marker::PhantomData<&'a mut [T]>,
+}
+
+// SAFETY: the view behaves like `&'a mut [T]`
+#[cfg(feature = "raw")]
+unsafe impl<T: Send> Send for RawSliceMut<'_, T> {}
+#[cfg(feature = "raw")]
+unsafe impl<T: Sync> Sync for RawSliceMut<'_, T> {}
+
+#[cfg(feature = "raw")]
+impl<'a, T> RawSliceMut<'a, T> {
+    #[inline]
+    pub fn new(slice: &'a mut [T]) -> Self {
+        Self {
+            len: slice.len(),
+            ptr: core::ptr::NonNull::from(slice).cast(),
+            __marker: core::marker::PhantomData,
+        }
+    }
+
+    /// # Safety
+    /// `ptr` and `len` must satisfy the requirements of [`core::slice::from_raw_parts_mut`] for
+    /// the lifetime `'a`.
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: core::ptr::NonNull<T>, len: usize) -> Self {
+        Self {
+            ptr,
+            len,
+            __marker: core::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the view has exclusive access to the elements for `'a`
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    #[inline]
+    pub fn into_mut_slice(self) -> &'a mut [T] {
+        // SAFETY: the view has exclusive access to the elements for `'a`
+        unsafe { core::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+#[cfg(feature = "raw")]
+impl<'short, T> ReborrowMut<'short> for RawSliceMut<'_, T> {
+    type Target = RawSliceMut<'short, T>;
+
+    #[inline]
+    fn rb_mut(&'short mut self) -> Self::Target {
+        // `self` is mutably borrowed for `'short`, so the new view has exclusive access to the
+        // elements for its whole lifetime.
+        RawSliceMut {
+            ptr: self.ptr,
+            len: self.len,
+            __marker: core::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "raw")]
+impl<'short, T> Reborrow<'short> for RawSliceMut<'_, T> {
+    type Target = &'short [T];
+
+    #[inline]
+    fn rb(&'short self) -> Self::Target {
+        // SAFETY: `self` is borrowed for `'short`, so the elements can't be mutated through it
+        unsafe { core::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+#[cfg(feature = "raw")]
+impl<'a, T> IntoConst for RawSliceMut<'a, T> {
+    type Target = &'a [T];
+
+    #[inline]
+    fn into_const(self) -> Self::Target {
+        self.into_mut_slice()
+    }
+}
+
+/// Reborrow an owning buffer as a mutable slice, leaving the buffer usable once the slice is
+/// dropped.
+pub trait AsSliceRbMut<T> {
+    #[must_use]
+    fn as_slice_rb_mut(&mut self) -> &mut [T];
+}
+
+#[cfg(feature = "alloc")]
+impl<T> AsSliceRbMut<T> for alloc::vec::Vec<T> {
+    #[inline]
+    fn as_slice_rb_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> AsSliceRbMut<T> for alloc::boxed::Box<[T]> {
+    #[inline]
+    fn as_slice_rb_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T> AsSliceRbMut<T> for &mut [T] {
+    #[inline]
+    fn as_slice_rb_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+/// Reborrow an owning string as a mutable string slice, leaving the string usable once the slice
+/// is dropped.
+pub trait AsStrRbMut {
+    #[must_use]
+    fn as_str_rb_mut(&mut self) -> &mut str;
+}
+
+#[cfg(feature = "alloc")]
+impl AsStrRbMut for alloc::string::String {
+    #[inline]
+    fn as_str_rb_mut(&mut self) -> &mut str {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsStrRbMut for alloc::boxed::Box<str> {
+    #[inline]
+    fn as_str_rb_mut(&mut self) -> &mut str {
+        self
+    }
+}
+
+impl AsStrRbMut for &mut str {
+    #[inline]
+    fn as_str_rb_mut(&mut self) -> &mut str {
+        self
+    }
+}
+
+/// Reborrow an optional slice view or buffer as an optional mutable slice, leaving it usable once
+/// the slice is dropped.
+pub trait AsOptSliceMut<T> {
+    #[must_use]
+    fn as_opt_slice_mut(&mut self) -> Option<&mut [T]>;
+}
+
+impl<T, S> AsOptSliceMut<T> for Option<S>
+where
+    S: AsSliceRbMut<T>,
+{
+    #[inline]
+    fn as_opt_slice_mut(&mut self) -> Option<&mut [T]> {
+        self.as_mut().map(S::as_slice_rb_mut)
+    }
+}
+
+/// Mutably reborrow the buffer of a copy-on-write slice, if it's owned.
+#[cfg(feature = "alloc")]
+pub trait CowSliceRbMut<T> {
+    /// Returns the owned buffer as a mutable slice, or `None` if the slice is borrowed. `self` is
+    /// usable again once the returned slice is dropped.
+    #[must_use]
+    fn rb_mut_if_owned(&mut self) -> Option<&mut [T]>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> CowSliceRbMut<T> for alloc::borrow::Cow<'_, [T]> {
+    #[inline]
+    fn rb_mut_if_owned(&mut self) -> Option<&mut [T]> {
+        match self {
+            alloc::borrow::Cow::Borrowed(_) => None,
+            alloc::borrow::Cow::Owned(v) => Some(v),
+        }
+    }
+}
+
+/// Mutably reborrow the contents of a ring buffer.
+#[cfg(feature = "alloc")]
+pub trait DequeRbMut<T> {
+    /// Returns the contents of the deque as two mutable slices, in order. The second slice is
+    /// non-empty only when the contents wrap around the end of the buffer. `self` is usable again
+    /// once the returned slices are dropped.
+    #[must_use]
+    fn rb_mut_slices(&mut self) -> (&mut [T], &mut [T]);
+}
+
+#[cfg(feature = "alloc")]
+impl<T> DequeRbMut<T> for alloc::collections::VecDeque<T> {
+    #[inline]
+    fn rb_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        self.as_mut_slices()
+    }
+}
+
+/// Extension methods for slice views.
+pub trait SliceViewExt<T> {
+    /// Reborrows a slice view with a single method call, without going through `&mut &mut [T]`.
+    /// The view is usable again once the returned slice is dropped.
+    #[must_use]
+    fn reborrow_mut(&mut self) -> &mut [T];
+}
+
+impl<T> SliceViewExt<T> for [T] {
+    #[inline]
+    fn reborrow_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn option() {
+        let mut a = 0;
+        let mut opt = Some(&mut a);
+        let opt_mut = &mut opt;
+        let _ = opt_mut.rb_mut();
+    }
+
+    #[test]
+    #[allow(clippy::drop_non_drop)]
+    fn custom_view_type() {
+        struct MyViewType<'a> {
+            r: &'a mut i32,
+        }
+
+        impl<'short, 'a> ReborrowMut<'short> for MyViewType<'a> {
+            type Target = MyViewType<'short>;
+
+            fn rb_mut(&'short mut self) -> Self::Target {
+                MyViewType { r: self.r }
+            }
+        }
+
+        fn takes_mut_option(_o: Option<MyViewType>) {}
+
+        let mut x = 0;
+        let mut o = Some(MyViewType { r: &mut x });
+        takes_mut_option(o.rb_mut());
+        takes_mut_option(o.rb_mut());
+        drop(o);
+    }
+
+    #[test]
+    fn as_ref() {
+        let v = [()];
+        let _r: &[()] = v.as_generalized_ref();
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_as_slice_rb_mut() {
+        use alloc::vec;
+
+        fn takes_slice(s: &mut [i32]) {
+            for x in s {
+                *x += 1;
+            }
+        }
+
+        let mut v = vec![1, 2, 3];
+        let view = &mut v;
+        takes_slice(view.as_slice_rb_mut());
+        takes_slice(view.as_slice_rb_mut());
+        view.push(4);
+        assert_eq!(v, [3, 4, 5, 4]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn box_dyn() {
+        use alloc::boxed::Box;
+
+        fn takes_iter(it: &mut dyn Iterator<Item = u8>) -> Option<u8> {
+            it.next()
+        }
+
+        let mut it: Box<dyn Iterator<Item = u8>> = Box::new(0..4);
+        assert_eq!(takes_iter(it.rb_mut()), Some(0));
+        assert_eq!(takes_iter(it.rb_mut()), Some(1));
+        assert_eq!(it.rb().size_hint(), (2, Some(2)));
+        assert_eq!(it.next(), Some(2));
+    }
+
+    #[test]
+    fn manual_copy_view_into_const() {
+        #[derive(Copy, Clone)]
+        struct SliceRef<'a> {
+            data: &'a [i32],
+        }
+
+        impl<'a> IntoConst for SliceRef<'a> {
+            type Target = SliceRef<'a>;
+
+            fn into_const(self) -> Self::Target {
+                self
+            }
+        }
+
+        let data = [1, 2, 3];
+        let view = SliceRef { data: &data };
+        let a = view.into_const();
+        let b = view.into_const();
+        assert_eq!(a.data, b.data);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn pin_box() {
+        use alloc::boxed::Box;
+        use core::pin::Pin;
+
+        fn incr(mut p: Pin<&mut i32>) {
+            *p += 1;
+        }
+
+        let mut p = Box::pin(0);
+        incr(p.rb_mut());
+        incr(p.rb_mut());
+        let r: Pin<&i32> = p.rb();
+        assert_eq!(*r, 2);
+    }
+
+    #[test]
+    fn builder_chain() {
+        struct Config {
+            width: u32,
+            height: u32,
+        }
+
+        struct ConfigMut<'a> {
+            cfg: &'a mut Config,
+        }
+
+        impl<'short, 'a> ReborrowMut<'short> for ConfigMut<'a> {
+            type Target = ConfigMut<'short>;
+
+            fn rb_mut(&'short mut self) -> Self::Target {
+                ConfigMut { cfg: self.cfg }
+            }
+        }
+
+        impl ConfigMut<'_> {
+            fn width(self, width: u32) -> Self {
+                self.cfg.width = width;
+                self
+            }
+
+            fn height(self, height: u32) -> Self {
+                self.cfg.height = height;
+                self
+            }
+        }
+
+        let mut cfg = Config {
+            width: 0,
+            height: 0,
+        };
+        let mut builder = ConfigMut { cfg: &mut cfg };
+        let _ = builder.rb_mut_keep().width(1).height(2);
+        let _ = builder.rb_mut_keep().width(3);
+        let _ = builder.height(4);
+        assert_eq!((cfg.width, cfg.height), (3, 4));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn path() {
+        use std::path::{Path, PathBuf};
+
+        fn file_name(p: &Path) -> Option<&str> {
+            p.file_name()?.to_str()
+        }
+
+        let buf = PathBuf::from("/tmp/file.txt");
+        assert_eq!(file_name(buf.rb()), Some("file.txt"));
+        assert_eq!(file_name(buf.rb()), Some("file.txt"));
+
+        let mut path: &Path = Path::new("/tmp/other.txt");
+        assert_eq!(file_name(path.rb()), Some("other.txt"));
+        assert_eq!(file_name(path.rb_mut()), Some("other.txt"));
+        assert_eq!(file_name(path), Some("other.txt"));
+    }
+
+    #[test]
+    fn array_of_dyn() {
+        // the trait object lifetime must be kept separate from the reference lifetime, since
+        // `&mut (dyn Trait + 'a)` is invariant over `'a`
+        fn drain<'a>(its: [&mut (dyn Iterator<Item = u8> + 'a); 2]) -> [Option<u8>; 2] {
+            its.map(|it| it.next())
+        }
+
+        let mut a = 0..3;
+        let mut b = 10..13;
+        let mut its: [&mut dyn Iterator<Item = u8>; 2] = [&mut a, &mut b];
+        assert_eq!(drain(its.rb_mut()), [Some(0), Some(10)]);
+        assert_eq!(drain(its.rb_mut()), [Some(1), Some(11)]);
+        assert_eq!(its.rb().map(|it| it.size_hint()), [(1, Some(1)); 2]);
+    }
+
+    #[test]
+    fn duration() {
+        use core::time::Duration;
+
+        #[derive(Copy, Clone, Debug, PartialEq)]
+        struct Timeout(Duration);
+
+        reborrow_copy_passthrough!(Timeout);
+
+        struct Timed<'a, T> {
+            value: &'a mut i32,
+            timeout: T,
+        }
+
+        impl<'short, 'a, T: ReborrowMut<'short>> ReborrowMut<'short> for Timed<'a, T> {
+            type Target = Timed<'short, T::Target>;
+
+            fn rb_mut(&'short mut self) -> Self::Target {
+                Timed {
+                    value: self.value,
+                    timeout: self.timeout.rb_mut(),
+                }
+            }
+        }
+
+        fn run(t: Timed<'_, Timeout>) {
+            *t.value += t.timeout.0.as_secs() as i32;
+        }
+
+        let mut x = 0;
+        let mut t = Timed {
+            value: &mut x,
+            timeout: Timeout(Duration::from_secs(2)),
+        };
+        run(t.rb_mut());
+        run(t.rb_mut());
+        assert_eq!(*t.value, 4);
+        assert_eq!(t.timeout.rb(), Timeout(Duration::from_secs(2)));
+        assert_eq!(t.timeout.into_const(), Timeout(Duration::from_secs(2)));
+
+        // reborrowing a reference to a duration must not copy the pointee
+        let mut d = Duration::from_secs(1);
+        let mut view = &mut d;
+        *view.rb_mut() += Duration::from_secs(1);
+        *view.rb_mut() += Duration::from_secs(1);
+        assert_eq!(d, Duration::from_secs(3));
+    }
+
+    #[cfg(feature = "either")]
+    #[test]
+    fn either() {
+        use either::Either;
+
+        fn incr(e: Either<&mut i32, &mut u8>) {
+            match e {
+                Either::Left(l) => *l += 1,
+                Either::Right(r) => *r += 1,
+            }
+        }
+
+        let mut a = 0i32;
+        let mut left: Either<&mut i32, &mut u8> = Either::Left(&mut a);
+        incr(left.rb_mut());
+        incr(left.rb_mut());
+        assert_eq!(left.rb(), Either::Left(&2));
+        assert_eq!(left.into_const(), Either::Left(&2));
+
+        let mut b = 0u8;
+        let mut right: Either<&mut i32, &mut u8> = Either::Right(&mut b);
+        incr(right.rb_mut());
+        incr(right.rb_mut());
+        assert_eq!(right.rb(), Either::Right(&2));
+        assert_eq!(right.into_const(), Either::Right(&2));
+    }
+
+    #[cfg(feature = "lazy")]
+    #[test]
+    #[allow(clippy::incompatible_msrv)]
+    fn lazy_cell() {
+        use core::cell::{Cell, LazyCell};
+
+        let calls = Cell::new(0);
+        let lazy = LazyCell::new(|| {
+            calls.set(calls.get() + 1);
+            42
+        });
+        assert_eq!(calls.get(), 0);
+        assert_eq!(*lazy.rb(), 42);
+        assert_eq!(*lazy.rb(), 42);
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[cfg(all(feature = "lazy", feature = "std"))]
+    #[test]
+    #[allow(clippy::incompatible_msrv)]
+    fn lazy_lock() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::LazyLock;
+
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+        static LAZY: LazyLock<i32> = LazyLock::new(|| {
+            CALLS.fetch_add(1, Ordering::Relaxed);
+            42
+        });
+
+        assert_eq!(*LAZY.rb(), 42);
+        assert_eq!(*LAZY.rb(), 42);
+        assert_eq!(CALLS.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn maybe_uninit_slice() {
+        use core::mem::MaybeUninit;
+
+        fn init(s: &mut [MaybeUninit<u8>], value: u8) {
+            for x in s {
+                x.write(value);
+            }
+        }
+
+        let mut storage = [MaybeUninit::<u8>::uninit(); 4];
+        let mut view: &mut [MaybeUninit<u8>] = &mut storage;
+        init(view.rb_mut(), 1);
+        init(&mut view.rb_mut()[2..], 2);
+        assert_eq!(view.rb().len(), 4);
+
+        #[cfg(feature = "raw")]
+        {
+            let init = unsafe { assume_init_mut_rb(&mut view) };
+            assert_eq!(init, [1, 1, 2, 2]);
+            init[0] = 3;
+            let init = unsafe { assume_init_mut_rb(&mut view) };
+            assert_eq!(init, [3, 1, 2, 2]);
+        }
+    }
+
+    #[test]
+    fn swap() {
+        let (mut x, mut y) = (1, 2);
+        let mut a = &mut x;
+        let mut b = &mut y;
+        swap_rb_mut(&mut a, &mut b);
+        assert_eq!((*a, *b), (2, 1));
+        swap_rb_mut(&mut a, &mut b);
+        *a += 10;
+        assert_eq!((x, y), (11, 2));
+    }
+
+    #[test]
+    fn replace() {
+        let mut x = 1;
+        let mut view = &mut x;
+        assert_eq!(replace_rb_mut(&mut view, 2), 1);
+        assert_eq!(replace_rb_mut(&mut view, 3), 2);
+        *view += 10;
+        assert_eq!(x, 13);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn io_slices() {
+        use std::io::{IoSlice, IoSliceMut};
+
+        fn fill(buf: &mut [u8], value: u8) {
+            buf.fill(value);
+        }
+
+        let mut storage = [0u8; 4];
+        let mut slice = IoSliceMut::new(&mut storage);
+        fill(slice.rb_mut(), 1);
+        fill(&mut slice.rb_mut()[2..], 2);
+        assert_eq!(slice.rb(), [1, 1, 2, 2]);
+
+        let shared = IoSlice::new(&storage);
+        assert_eq!(shared.rb(), [1, 1, 2, 2]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn dyn_reborrow_mut() {
+        use alloc::boxed::Box;
+
+        let mut x = 0;
+        let mut by_ref = &mut x;
+        let mut boxed = Box::new(10);
+        let mut views: [&mut dyn DynReborrowMut<i32>; 2] = [&mut by_ref, &mut boxed];
+
+        for _ in 0..2 {
+            for view in views.rb_mut() {
+                *view.dyn_rb_mut() += 1;
+            }
+        }
+        assert_eq!(*by_ref, 2);
+        assert_eq!(*boxed, 12);
+    }
+
+    #[test]
+    fn cell_slice() {
+        use core::cell::Cell;
+
+        fn incr_all(cells: &Cell<[i32]>) {
+            for c in cells.as_slice_of_cells() {
+                c.set(c.get() + 1);
+            }
+        }
+
+        let mut data = [1, 2, 3];
+        let cells: &Cell<[i32]> = Cell::from_mut(&mut data[..]);
+        incr_all(cells.rb());
+        incr_all(cells.rb());
+
+        let owned = Cell::new(0);
+        owned.rb().set(1);
+        owned.rb().set(owned.get() + 1);
+        assert_eq!(owned.get(), 2);
+        assert_eq!(data, [3, 4, 5]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn fold() {
+        use alloc::vec;
+
+        let (mut a, mut b, mut c) = (1, 2, 3);
+        let mut views = vec![&mut a, &mut b, &mut c];
+        let sum = fold_rb_mut(&mut views, 0, |acc, x| {
+            *x += 1;
+            acc + *x
+        });
+        assert_eq!(sum, 9);
+        let sum = fold_rb_mut(&mut views, 0, |acc, x| acc + *x);
+        assert_eq!(sum, 9);
+        views.pop();
+        *views[0] = 0;
+        assert_eq!((a, b, c), (0, 3, 4));
+    }
+
+    #[test]
+    fn iter_mut() {
+        let (mut a, mut b) = (1, 2);
+        let mut views = [&mut a, &mut b];
+        for x in rb_iter_mut(&mut views) {
+            *x += 1;
+        }
+        for x in rb_iter_mut(&mut views) {
+            *x *= 2;
+        }
+        assert_eq!(views.rb().map(|x| *x), [4, 6]);
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn smallvec() {
+        use smallvec::{smallvec, SmallVec};
+
+        let (mut a, mut b) = (1, 2);
+        let mut views: SmallVec<[&mut i32; 4]> = smallvec![&mut a, &mut b];
+        for x in rb_iter_mut(&mut views) {
+            *x += 1;
+        }
+        for x in views.rb_mut() {
+            *x += 1;
+        }
+        {
+            let r: SmallVec<[&i32; 4]> = views.rb();
+            assert_eq!(r.as_slice(), [&3, &4]);
+        }
+        assert_eq!(views.into_const().as_slice(), [&3, &4]);
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn arrayvec() {
+        use arrayvec::ArrayVec;
+
+        let (mut a, mut b) = (1, 2);
+        let mut views: ArrayVec<&mut i32, 4> = ArrayVec::new();
+        views.push(&mut a);
+        views.push(&mut b);
+        for x in rb_iter_mut(&mut views) {
+            *x += 1;
+        }
+        for x in views.rb_mut() {
+            *x += 1;
+        }
+        {
+            let r: ArrayVec<&i32, 4> = views.rb();
+            assert_eq!(r.as_slice(), [&3, &4]);
+        }
+        assert_eq!(views.into_const().as_slice(), [&3, &4]);
+    }
+
+    #[test]
+    fn try_rb_mut() {
+        use core::cell::{RefCell, RefMut};
+
+        struct SharedMut<'a>(&'a RefCell<i32>);
+
+        impl<'short, 'a> TryReborrowMut<'short> for SharedMut<'a> {
+            type Target = RefMut<'short, i32>;
+            type Error = core::cell::BorrowMutError;
+
+            fn try_rb_mut(&'short mut self) -> Result<Self::Target, Self::Error> {
+                self.0.try_borrow_mut()
+            }
+        }
+
+        let cell = RefCell::new(0);
+        let mut a = SharedMut(&cell);
+        let mut b = Some(SharedMut(&cell));
+
+        {
+            let mut guard = a.try_rb_mut().unwrap();
+            *guard += 1;
+            assert!(b.as_mut().unwrap().try_rb_mut().is_err());
+            assert!(try_rb_mut_option(&mut b).is_err());
+        }
+        *try_rb_mut_option(&mut b).unwrap().unwrap() += 1;
+        assert!(try_rb_mut_option(&mut None::<SharedMut>).unwrap().is_none());
+        assert_eq!(*cell.borrow(), 2);
+
+        let mut x = 0;
+        let mut view = &mut x;
+        let Ok(r) = view.try_rb_mut();
+        *r += 1;
+        assert_eq!(x, 1);
+    }
+
+    #[test]
+    fn result() {
+        use core::convert::Infallible;
+
+        fn incr(r: Result<&mut i32, Infallible>) {
+            let Ok(x) = r;
+            *x += 1;
+        }
+
+        let mut x = 0;
+        let mut r: Result<&mut i32, Infallible> = Ok(&mut x);
+        incr(r.rb_mut());
+        incr(r.rb_mut());
+        assert_eq!(r.rb(), Ok(&2));
+        assert_eq!(r.into_const(), Ok(&2));
+
+        let mut y = 0;
+        let mut e: Result<&mut i32, &mut i32> = Err(&mut y);
+        if let Err(y) = e.rb_mut() {
+            *y += 1;
+        }
+        assert_eq!(e.into_const(), Err(&1));
+    }
+
+    #[test]
+    fn dyn_any_send_sync() {
+        use core::any::Any;
+
+        fn set(x: &mut (dyn Any + Send + Sync)) {
+            *x.downcast_mut::<i32>().unwrap() += 1;
+        }
+
+        let mut x = 0i32;
+        let mut view: &mut (dyn Any + Send + Sync) = &mut x;
+        set(view.rb_mut());
+        set(view.rb_mut());
+        let r: &(dyn Any + Send + Sync) = view.rb();
+        assert_eq!(r.downcast_ref::<i32>(), Some(&2));
+    }
+
+    #[test]
+    fn for_each() {
+        let (mut a, mut b, mut c) = (0, 1, 2);
+        let mut views = [&mut a, &mut b, &mut c];
+        for_each_rb_mut(&mut views, |x| *x += 1);
+        for_each_rb_mut(&mut views, |x| *x *= 2);
+        assert_eq!(views.rb().map(|x| *x), [2, 4, 6]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn as_ref_option_result() {
+        use alloc::vec;
+        use alloc::vec::Vec;
+
+        let mut o = Some(vec![()]);
+        let r: Option<&[()]> = o.as_generalized_ref();
+        assert_eq!(r, Some(&[()][..]));
+        let m: Option<&mut [()]> = o.as_generalized_mut();
+        assert_eq!(m.map(|s| s.len()), Some(1));
+
+        let mut r: Result<Vec<()>, i32> = Ok(vec![(), ()]);
+        let s: Result<&[()], &i32> = r.as_generalized_ref();
+        assert_eq!(s, Ok(&[(), ()][..]));
+        let m: Result<&mut [()], &mut i32> = r.as_generalized_mut();
+        assert_eq!(m.map(|s| s.len()), Ok(2));
+
+        let mut e: Result<Vec<()>, i32> = Err(1);
+        let m: Result<&mut [()], &mut i32> = e.as_generalized_mut();
+        *m.unwrap_err() += 1;
+        assert_eq!(e, Err(2));
+    }
+
+    #[test]
+    fn ref_mut() {
+        use core::cell::{RefCell, RefMut};
+
+        fn incr(x: &mut i32) {
+            *x += 1;
+        }
+
+        let cell = RefCell::new((0, 0));
+        {
+            let mut guard = RefMut::map(cell.borrow_mut(), |pair| &mut pair.1);
+            incr(guard.rb_mut());
+            incr(guard.rb_mut());
+            assert_eq!(*guard.rb(), 2);
+            assert!(cell.try_borrow().is_err());
+        }
+        assert_eq!(*cell.borrow().rb(), (0, 2));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn cow_slice() {
+        use alloc::borrow::Cow;
+        use alloc::vec;
+
+        fn sum(bytes: &[u8]) -> u32 {
+            bytes.iter().map(|&b| b as u32).sum()
+        }
+
+        let borrowed: Cow<'_, [u8]> = Cow::Borrowed(&[1, 2]);
+        let owned: Cow<'_, [u8]> = Cow::Owned(vec![3, 4]);
+        assert_eq!(sum(borrowed.rb()), 3);
+        assert_eq!(sum(borrowed.rb()), 3);
+        assert_eq!(sum(owned.rb()), 7);
+        assert_eq!(sum(owned.rb()), 7);
+    }
+
+    #[test]
+    fn index() {
+        let mut x = [1, 2, 3];
+        let mut view: &mut [i32] = &mut x;
+        *index_rb_mut(&mut view, 0) += 1;
+        index_rb_mut(&mut view, 1..).fill(0);
+        *index_rb_mut(&mut view, 2) += 1;
+        assert_eq!(view, [2, 0, 1]);
+    }
+
+    #[test]
+    fn primitive_refs() {
+        // reborrowing a reference to a primitive must not copy the pointee
+        let (mut a, mut b, mut c) = (0i32, 0.0f64, false);
+        let (mut a, mut b, mut c) = (&mut a, &mut b, &mut c);
+        for _ in 0..2 {
+            *a.rb_mut() += 1;
+            *b.rb_mut() += 0.5;
+            let c = c.rb_mut();
+            *c = !*c;
+        }
+        assert_eq!((*a.rb(), *b.rb(), *c.rb()), (2, 1.0, false));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_map_values() {
+        use std::collections::HashMap;
+        use std::string::String;
+
+        let (mut a, mut b) = (1, 2);
+        let mut map: HashMap<String, &mut i32> = HashMap::new();
+        map.insert("a".into(), &mut a);
+        map.insert("b".into(), &mut b);
+
+        for (k, v) in rb_values_mut(&mut map) {
+            *v += if k == "a" { 10 } else { 20 };
+        }
+        for (_, v) in rb_values_mut(&mut map) {
+            *v *= 2;
+        }
+        assert_eq!((*map["a"], *map["b"]), (22, 44));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn boxed_slice_as_slice_rb_mut() {
+        use alloc::boxed::Box;
+        use alloc::vec;
+
+        fn incr(s: &mut [u8]) {
+            s.iter_mut().for_each(|x| *x += 1);
+        }
+
+        let mut b: Box<[u8]> = vec![0, 1].into_boxed_slice();
+        let view = &mut b;
+        incr(view.as_slice_rb_mut());
+        incr(view.as_slice_rb_mut());
+        assert_eq!(*b, [2, 3]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn sets() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let hash: HashSet<i32> = [1, 2].into_iter().collect();
+        let btree: BTreeSet<i32> = [2, 3].into_iter().collect();
+
+        let (h, b) = (hash.rb(), btree.rb());
+        let in_both = |x: &i32| h.contains(x) && b.contains(x);
+        let (h, b) = (hash.rb(), btree.rb());
+        let in_either = |x: &i32| h.contains(x) || b.contains(x);
+
+        assert_eq!((1..=3).filter(in_both).count(), 1);
+        assert_eq!((1..=3).filter(in_either).count(), 3);
+    }
+
+    #[cfg(all(feature = "rayon", feature = "std"))]
+    #[test]
+    fn par_iter() {
+        use rayon::iter::ParallelIterator;
+        use std::vec::Vec;
+
+        let mut x: Vec<i32> = (0..64).collect();
+        let mut views: Vec<&mut i32> = x.iter_mut().collect();
+        rb_par_iter_mut(&mut views).for_each(|x| *x += 1);
+        rb_par_iter_mut(&mut views).for_each(|x| *x *= 2);
+        drop(views);
+        assert!(x.iter().enumerate().all(|(i, &x)| x == 2 * (i as i32 + 1)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn collect() {
+        use alloc::vec::Vec;
+
+        fn incr_all(views: Vec<&mut i32>) {
+            for x in views {
+                *x += 1;
+            }
+        }
+
+        let (mut a, mut b) = (0, 1);
+        let views: &mut [&mut i32] = &mut [&mut a, &mut b];
+        incr_all(collect_rb_mut(views));
+        incr_all(collect_rb_mut(views));
+        assert_eq!((a, b), (2, 3));
+    }
+
+    #[test]
+    fn map_ref_mut_field() {
+        use core::cell::RefCell;
+
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        let cell = RefCell::new(Point { x: 0, y: 0 });
+        let mut guard = cell.borrow_mut();
+        *map_ref_mut(&mut guard, |p| &mut p.x) += 1;
+        *map_ref_mut(&mut guard, |p| &mut p.y) += 2;
+        *map_ref_mut(&mut guard, |p| &mut p.x) += 3;
+        drop(guard);
+        let p = cell.borrow();
+        assert_eq!((p.x, p.y), (4, 2));
+    }
+
+    #[test]
+    fn slice_iter() {
+        let mut x = [1, 2, 3];
+        let mut iter = x.iter_mut();
+        *iter.next().unwrap() += 10;
+        assert_eq!(iter.rb().sum::<i32>(), 5);
+        assert_eq!(iter.rb().rb().count(), 2);
+        for x in iter {
+            *x += 10;
+        }
+        assert_eq!(x, [11, 12, 13]);
+
+        let mut iter = x.iter();
+        iter.next();
+        assert_eq!(iter.rb().as_slice(), [12, 13]);
+        assert_eq!(iter.next(), Some(&12));
+    }
+
+    #[test]
+    fn tuple_and_array_into_const() {
+        let (mut a, mut b) = (0i32, 0u8);
+        let mut t = (&mut a, &mut b);
+        *t.rb_mut().0 += 1;
+        *t.rb_mut().1 += 2;
+        assert_eq!(t.rb(), (&1, &2));
+        let t: (&i32, &u8) = t.into_const();
+        assert_eq!(t, (&1, &2));
+
+        let (mut a, mut b, mut c) = (0, 1, 2);
+        let mut arr = [&mut a, &mut b, &mut c];
+        for x in arr.rb_mut() {
+            *x += 1;
+        }
+        let arr: [&i32; 3] = arr.into_const();
+        assert_eq!(arr, [&1, &2, &3]);
+    }
+
+    #[test]
+    fn slice_view_ext() {
+        fn axpy(alpha: f64, x: &[f64], y: &mut [f64]) {
+            for (y, x) in y.iter_mut().zip(x) {
+                *y += alpha * x;
+            }
+        }
+
+        let x = [1.0, 2.0];
+        let mut y = [0.0; 2];
+        let view: &mut [f64] = &mut y;
+        for _ in 0..3 {
+            axpy(0.5, &x, view.reborrow_mut());
+        }
+        axpy(1.0, &x, view);
+        assert_eq!(y, [2.5, 5.0]);
+    }
+
+    #[cfg(feature = "raw")]
+    #[test]
+    fn raw_slice() {
+        fn double(mut s: RawSliceMut<'_, i32>) {
+            s.as_mut_slice().iter_mut().for_each(|x| *x *= 2);
+        }
+
+        let mut x = [1, 2, 3];
+        let mut view = RawSliceMut::new(&mut x);
+        double(view.rb_mut());
+        double(view.rb_mut());
+        assert_eq!(view.rb(), [4, 8, 12]);
+        assert_eq!((view.len(), view.is_empty()), (3, false));
+        assert_eq!(view.into_const(), [4, 8, 12]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn c_string() {
+        use alloc::ffi::CString;
+        use core::ffi::CStr;
+
+        fn len(s: &CStr) -> usize {
+            s.to_bytes().len()
+        }
+
+        let s = CString::new("abc").unwrap();
+        assert_eq!(len(s.rb()), 3);
+        assert_eq!(len(s.rb()), 3);
+        let r: &CStr = s.rb();
+        assert_eq!(r.rb().to_bytes(), b"abc");
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn string() {
+        use alloc::string::String;
+
+        fn shout(s: &mut str) {
+            s.make_ascii_uppercase();
+        }
+
+        let mut s = String::from("abc");
+        let view = &mut s;
+        shout(view.as_str_rb_mut());
+        shout(view.as_str_rb_mut());
+        view.push('d');
+        assert_eq!(s, "ABCd");
+
+        // reborrowing a reference to a string must not lose access to the string itself
+        let mut view = &mut s;
+        view.rb_mut().push('e');
+        let r: &String = view.rb();
+        assert_eq!(r, "ABCde");
+
+        let mut slice: &mut str = s.as_mut_str();
+        shout(slice.as_str_rb_mut());
+        assert_eq!(slice, "ABCDE");
+    }
+
+    #[test]
+    fn modify() {
+        let mut x = 0;
+        let mut o = Some(&mut x);
+        o.modify_rb_mut(|o| *o.unwrap() += 1);
+        let y = o.modify_rb_mut(|o| {
+            let o = o.unwrap();
+            *o += 1;
+            *o
+        });
+        assert_eq!(y, 2);
+        assert_eq!(o, Some(&mut 2));
+    }
+
+    #[test]
+    fn opt_slice() {
+        fn fill(s: Option<&mut [u8]>, value: u8) -> bool {
+            s.map(|s| s.fill(value)).is_some()
+        }
+
+        let mut x = [0u8; 2];
+        let mut some: Option<&mut [u8]> = Some(&mut x);
+        let mut none: Option<&mut [u8]> = None;
+        assert!(fill(some.as_opt_slice_mut(), 1));
+        assert!(fill(some.as_opt_slice_mut(), 2));
+        assert!(!fill(none.as_opt_slice_mut(), 1));
+        assert!(!fill(none.as_opt_slice_mut(), 2));
+        assert_eq!(some.as_deref(), Some(&[2u8, 2][..]));
+    }
+
+    #[cfg(feature = "either")]
+    #[test]
+    fn either_select() {
+        use either::Either;
+
+        let (mut x, mut y) = (0i32, 0u8);
+        let (mut a, mut b) = (&mut x, Some(&mut y));
+        for cond in [true, false, true] {
+            match either_rb_mut(cond, &mut a, &mut b) {
+                Either::Left(a) => *a += 1,
+                Either::Right(b) => *b.unwrap() += 1,
+            }
+        }
+        *a += 1;
+        **b.as_mut().unwrap() += 1;
+        assert_eq!((x, y), (3, 2));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn cow_rb_mut_if_owned() {
+        use alloc::borrow::Cow;
+        use alloc::vec;
+
+        let mut owned: Cow<'_, [u8]> = Cow::Owned(vec![1, 2]);
+        owned.rb_mut_if_owned().unwrap()[0] = 3;
+        owned.rb_mut_if_owned().unwrap()[1] = 4;
+        assert_eq!(*owned, [3, 4]);
+
+        let mut borrowed: Cow<'_, [u8]> = Cow::Borrowed(&[1, 2]);
+        assert!(borrowed.rb_mut_if_owned().is_none());
+        assert!(borrowed.rb_mut_if_owned().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn atomics() {
+        use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+        let count = AtomicUsize::new(0);
+        let total = AtomicU64::new(0);
+        let (count_view, total_view) = (&count, &total);
+
+        std::thread::scope(|s| {
+            for i in 0..2 {
+                let (count, total) = (count_view.rb(), total_view.rb());
+                s.spawn(move || {
+                    count.fetch_add(1, Ordering::Relaxed);
+                    total.fetch_add(i + 1, Ordering::Relaxed);
+                });
+            }
+            let count = count.rb();
+            s.spawn(move || count.fetch_add(1, Ordering::Relaxed));
+        });
+        assert_eq!(count.load(Ordering::Relaxed), 3);
+        assert_eq!(total.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn try_map() {
+        fn parse_into(x: &mut i32, s: &str) -> Result<(), core::num::ParseIntError> {
+            *x += s.parse::<i32>()?;
+            Ok(())
+        }
+
+        let mut x = 0;
+        let mut view = &mut x;
+        assert!(try_map_rb_mut(&mut view, |x| parse_into(x, "2")).is_ok());
+        assert!(try_map_rb_mut(&mut view, |x| parse_into(x, "two")).is_err());
+        *view += 1;
+        assert_eq!(x, 3);
+    }
+
+    #[cfg(feature = "generic-array")]
+    #[test]
+    fn generic_array() {
+        use generic_array::typenum::U4;
+        use generic_array::GenericArray;
+
+        let mut x = [0u8, 1, 2, 3];
+        let [a, b, c, d] = &mut x;
+        let mut views: GenericArray<&mut u8, U4> = GenericArray::from([a, b, c, d]);
+        for x in views.rb_mut() {
+            *x += 1;
+        }
+        for x in views.rb_mut() {
+            *x *= 2;
+        }
+        assert_eq!(views.rb().as_slice(), [&2, &4, &6, &8]);
+        assert_eq!(views.into_const().as_slice(), [&2, &4, &6, &8]);
+    }
+
+    #[test]
+    fn ranges() {
+        let mut full = ..;
+        assert_eq!(full.rb_mut(), ..);
+        assert_eq!(full.rb(), ..);
+        assert_eq!(full.into_const(), ..);
+
+        // reborrowing a reference to a range must advance the range itself
+        let mut range = 0..3;
+        let mut view = &mut range;
+        assert_eq!(view.rb_mut().next(), Some(0));
+        assert_eq!(view.rb_mut().next(), Some(1));
+        assert_eq!(range, 2..3);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn sort() {
+        let (mut a, mut b, mut c) = (3, 1, 2);
+        let mut views = [&mut a, &mut b, &mut c];
+        sort_rb_by(&mut views, |x, y| x.cmp(y));
+        assert_eq!(views.rb().map(|x| *x), [1, 2, 3]);
+        sort_rb_by(&mut views, |x, y| y.cmp(x));
+        *views[0] += 1;
+        assert_eq!((a, b, c), (4, 1, 2));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn deque_rb_mut_slices() {
+        let mut deque = alloc::collections::VecDeque::with_capacity(4);
+        deque.extend([1, 2, 3, 4]);
+        deque.pop_front();
+        deque.pop_front();
+        deque.push_back(5);
+        deque.push_back(6);
+
+        let view = &mut deque;
+        {
+            let (front, back) = view.rb_mut_slices();
+            assert_eq!((front.len(), back.len()), (2, 2));
+            front[0] *= 10;
+            back[1] *= 10;
+        }
+        view.push_front(0);
+        assert!(deque.iter().eq(&[0, 30, 4, 5, 60]));
+    }
+
+    #[test]
+    fn option_pin_mut() {
+        use core::marker::PhantomPinned;
+        use core::pin::{pin, Pin};
+
+        struct Counter {
+            count: u32,
+            _pinned: PhantomPinned,
+        }
+
+        fn incr(view: Option<Pin<&mut Counter>>) {
+            if let Some(counter) = view {
+                // SAFETY: `count` isn't structurally pinned.
+                unsafe { counter.get_unchecked_mut().count += 1 };
+            }
+        }
+
+        let counter = pin!(Counter {
+            count: 0,
+            _pinned: PhantomPinned,
+        });
+        let mut view = Some(counter);
+        incr(view.rb_mut());
+        incr(view.rb_mut());
+        let r: Option<Pin<&Counter>> = view.rb();
+        assert_eq!(r.map(|c| c.count), Some(2));
+        let r: Option<Pin<&Counter>> = view.into_const();
+        assert_eq!(r.map(|c| c.count), Some(2));
+    }
+
+    #[test]
+    fn chunks() {
+        let mut x = [1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut view: &mut [f64] = &mut x;
+        for chunk in rb_chunks_mut(&mut view, 2) {
+            chunk[0] *= 10.0;
+        }
+        for chunk in rb_chunks_mut(&mut view, 2) {
+            let last = chunk.len() - 1;
+            chunk[last] += 0.5;
+        }
+        view[0] += 1.0;
+        assert_eq!(x, [11.0, 2.5, 30.0, 4.5, 50.5]);
     }
 }
@@ -0,0 +1,9 @@
+use reborrow::ReborrowMut;
+
+fn main() {
+    let mut x = 0;
+    let mut o = Some(&mut x);
+    let a = o.rb_mut();
+    let b = o.rb_mut();
+    drop((a, b));
+}
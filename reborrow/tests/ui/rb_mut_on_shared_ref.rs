@@ -0,0 +1,7 @@
+use reborrow::ReborrowMut;
+
+fn main() {
+    let x = 0;
+    let mut r = &x;
+    let _m: &mut i32 = r.rb_mut();
+}
@@ -0,0 +1,11 @@
+use reborrow::ReborrowMut;
+
+fn extend<'a>(o: &mut Option<&'a mut i32>) -> Option<&'a mut i32> {
+    o.rb_mut()
+}
+
+fn main() {
+    let mut x = 0;
+    let mut o = Some(&mut x);
+    let _ = extend(&mut o);
+}
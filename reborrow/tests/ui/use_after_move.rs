@@ -0,0 +1,8 @@
+fn takes_mut_option(_o: Option<&mut i32>) {}
+
+fn main() {
+    let mut x = 0;
+    let o = Some(&mut x);
+    takes_mut_option(o);
+    takes_mut_option(o);
+}